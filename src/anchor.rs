@@ -3,6 +3,7 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::collections::HashMap;
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
@@ -32,6 +33,71 @@ impl fmt::Display for Type {
     }
 }
 
+/// The rules by which anchors are auto-generated from (Markdown) titles.
+/// With either, repeated titles within a document get `-1`, `-2`, ... appended.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub enum SlugScheme {
+    /// GitHub Flavored Markdown:
+    ///
+    /// 1. downcase the title
+    /// 2. remove anything that is not a letter, number, space, hyphen or underscore
+    /// 3. change any space to a hyphen
+    #[default]
+    Gfm,
+    /// Pandoc:
+    ///
+    /// 1. downcase the title
+    /// 2. remove anything that is not a letter, number, space,
+    ///    hyphen, underscore or period
+    /// 3. change any space or newline to a hyphen
+    /// 4. remove everything up to the first letter
+    /// 5. if nothing is left, use `section`
+    Pandoc,
+}
+
+impl SlugScheme {
+    /// Generates the anchor name for a title.
+    #[must_use]
+    pub fn slugify(self, title: &str) -> String {
+        let pandoc = self == Self::Pandoc;
+        let slug: String = title
+            .to_lowercase()
+            .chars()
+            .filter_map(|chr| match chr {
+                ' ' => Some('-'),
+                '\t' | '\n' if pandoc => Some('-'),
+                '-' | '_' => Some(chr),
+                '.' if pandoc => Some(chr),
+                _ if chr.is_alphanumeric() => Some(chr),
+                _ => None,
+            })
+            .collect();
+        match self {
+            Self::Gfm => slug,
+            Self::Pandoc => {
+                let from_first_letter = slug.trim_start_matches(|chr: char| !chr.is_alphabetic());
+                if from_first_letter.is_empty() {
+                    "section".to_owned()
+                } else {
+                    from_first_letter.to_owned()
+                }
+            }
+        }
+    }
+}
+
+/// Makes an anchor name unique within a document,
+/// by appending `-1`, `-2`, ... to repeated names.
+pub(crate) fn deduplicate(name: String, used: &mut HashMap<String, usize>) -> String {
+    let count = used.entry(name.clone()).or_insert(0);
+    *count += 1;
+    if *count == 1 {
+        name
+    } else {
+        format!("{name}-{}", *count - 1)
+    }
+}
+
 /// Anchor target found in markup files
 ///
 /// In HTML, these look like:
@@ -77,3 +143,39 @@ impl fmt::Display for Anchor {
         write!(f, "{}#{}", self.source, self.name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_gfm() {
+        assert_eq!(
+            SlugScheme::Gfm.slugify("My 1. @#%^$^-cool header!!"),
+            "my-1--cool-header"
+        );
+        assert_eq!(SlugScheme::Gfm.slugify("Foo_Bar Über"), "foo_bar-über");
+        assert_eq!(
+            SlugScheme::Gfm.slugify("Tab\tand\nnewline"),
+            "tabandnewline"
+        );
+    }
+
+    #[test]
+    fn slugify_pandoc() {
+        assert_eq!(
+            SlugScheme::Pandoc.slugify("1. Getting started v1.2"),
+            "getting-started-v1.2"
+        );
+        assert_eq!(SlugScheme::Pandoc.slugify("2024"), "section");
+        assert_eq!(SlugScheme::Pandoc.slugify("Two\nlines"), "two-lines");
+    }
+
+    #[test]
+    fn deduplicate_names() {
+        let mut used = HashMap::new();
+        assert_eq!(deduplicate("intro".to_owned(), &mut used), "intro");
+        assert_eq!(deduplicate("intro".to_owned(), &mut used), "intro-1");
+        assert_eq!(deduplicate("intro".to_owned(), &mut used), "intro-2");
+    }
+}
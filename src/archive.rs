@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Reading markup files from within archives and compressed files,
+//! completely in memory.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+/// The separator between the path of an archive
+/// and the path of a file within it, e.g. `docs.zip!/index.html`.
+pub const PATH_SEPARATOR: &str = "!/";
+
+/// A supported archive or compression format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// An uncompressed tar archive
+    Tar,
+    /// A gzip compressed tar archive
+    TarGz,
+    /// A zip archive
+    Zip,
+    /// A single, gzip compressed file, e.g. `README.md.gz`
+    Gz,
+}
+
+impl Format {
+    /// Figures out the format of a file,
+    /// judging from its (case-insensitive) file extension.
+    #[must_use]
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let path = Path::new(file_name);
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "tgz" => Some(Self::TarGz),
+            "tar" => Some(Self::Tar),
+            "zip" => Some(Self::Zip),
+            "gz" => {
+                let inner_is_tar = Path::new(path.file_stem()?)
+                    .extension()
+                    .is_some_and(|inner_ext| inner_ext.eq_ignore_ascii_case("tar"));
+                Some(if inner_is_tar { Self::TarGz } else { Self::Gz })
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether this format may contain multiple files.
+    #[must_use]
+    pub const fn is_archive(self) -> bool {
+        !matches!(self, Self::Gz)
+    }
+}
+
+/// A regular file within an archive.
+#[derive(Debug)]
+pub struct Entry {
+    /// The path of the file within the archive, without a leading `/`
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+fn read_tar_entries(reader: impl Read) -> io::Result<Vec<Entry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = vec![];
+    for entry_res in archive.entries()? {
+        let mut entry = entry_res?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        entries.push(Entry { path, content });
+    }
+    Ok(entries)
+}
+
+fn read_zip_entries(file: fs::File) -> io::Result<Vec<Entry>> {
+    let mut archive = zip::ZipArchive::new(file).map_err(io::Error::other)?;
+    let mut entries = vec![];
+    for idx in 0..archive.len() {
+        let mut entry = archive.by_index(idx).map_err(io::Error::other)?;
+        if !entry.is_file() {
+            continue;
+        }
+        let path = entry.name().to_owned();
+        let mut content = vec![];
+        entry.read_to_end(&mut content)?;
+        entries.push(Entry { path, content });
+    }
+    Ok(entries)
+}
+
+/// Reads all regular files from an archive.
+/// For [`Format::Gz`], this returns a single entry
+/// named like the archive without the `.gz` suffix.
+///
+/// # Errors
+///
+/// If reading or decompressing the archive failed.
+pub fn read_entries(archive: &Path, format: Format) -> io::Result<Vec<Entry>> {
+    log::debug!("Reading archive '{}' ...", archive.display());
+    let mut entries = match format {
+        Format::Tar => read_tar_entries(fs::File::open(archive)?)?,
+        Format::TarGz => read_tar_entries(GzDecoder::new(fs::File::open(archive)?))?,
+        Format::Zip => read_zip_entries(fs::File::open(archive)?)?,
+        Format::Gz => {
+            let content = decompress_gz(archive)?;
+            let file_name = archive
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let path = file_name
+                .get(..file_name.len().saturating_sub(".gz".len()))
+                .unwrap_or_default()
+                .to_owned();
+            vec![Entry { path, content }]
+        }
+    };
+    for entry in &mut entries {
+        entry.path = entry.path.trim_start_matches("./").to_owned();
+    }
+    Ok(entries)
+}
+
+/// Decompresses a single, gzip compressed file.
+///
+/// # Errors
+///
+/// If reading or decompressing the file failed.
+pub fn decompress_gz(path: &Path) -> io::Result<Vec<u8>> {
+    let mut content = vec![];
+    GzDecoder::new(fs::File::open(path)?).read_to_end(&mut content)?;
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_from_file_name() {
+        assert_eq!(Format::from_file_name("docs.zip"), Some(Format::Zip));
+        assert_eq!(Format::from_file_name("docs.TAR.GZ"), Some(Format::TarGz));
+        assert_eq!(Format::from_file_name("docs.tgz"), Some(Format::TarGz));
+        assert_eq!(Format::from_file_name("docs.tar"), Some(Format::Tar));
+        assert_eq!(Format::from_file_name("README.md.gz"), Some(Format::Gz));
+        assert_eq!(Format::from_file_name("README.md"), None);
+        assert!(!Format::Gz.is_archive());
+    }
+}
@@ -0,0 +1,117 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! The inverse of the list of links:
+//! For each linked file and anchor, where it is linked from.
+
+use std::collections::BTreeMap;
+
+use crate::graph::Documents;
+use crate::link::{Link, Locator, Target};
+
+/// All the links to one file, or to one anchor within it.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Entry {
+    /// The normalized path of the linked local file,
+    /// or the linked URL without its fragment
+    pub file: String,
+    /// The linked anchor, if any
+    pub anchor: Option<String>,
+    /// Where the links were found, in the order they were extracted
+    pub sources: Vec<Locator>,
+}
+
+impl Entry {
+    /// The linked file, followed by `#` and the anchor if there is one.
+    #[must_use]
+    pub fn target(&self) -> String {
+        self.anchor.as_ref().map_or_else(
+            || self.file.clone(),
+            |anchor| format!("{}#{anchor}", self.file),
+        )
+    }
+}
+
+/// The key under which a link is indexed;
+/// `None` for invalid targets.
+fn target_file(link: &Link, documents: &Documents) -> Option<String> {
+    match &link.target {
+        Target::FileSystem(_) | Target::FileUrl(_) => documents.target_id(link),
+        Target::Invalid(_) => None,
+        _ => Some(link.target.without_fragment().to_string()),
+    }
+}
+
+/// Inverts `links` into a backlinks index,
+/// sorted by linked file, with the links to the file as a whole
+/// before those to its anchors.
+///
+/// Relative targets are resolved against the file they were found in,
+/// and links to fragments within the same document
+/// are indexed under that document.
+#[must_use]
+pub fn index(links: &[Link]) -> Vec<Entry> {
+    let mut entries: BTreeMap<(String, Option<String>), Vec<Locator>> = BTreeMap::new();
+    let documents = Documents::new(links.iter().map(|link| link.source.file.as_ref()));
+    for link in links {
+        let Some(file) = target_file(link, &documents) else {
+            log::debug!("Not indexing link with invalid target: {link}");
+            continue;
+        };
+        let anchor = link
+            .target
+            .fragment()
+            .filter(|frag| !frag.is_empty())
+            .map(ToOwned::to_owned);
+        entries
+            .entry((file, anchor))
+            .or_default()
+            .push(link.source.clone());
+    }
+    entries
+        .into_iter()
+        .map(|((file, anchor), sources)| Entry {
+            file,
+            anchor,
+            sources,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{FileLoc, Position};
+    use std::sync::Arc;
+
+    fn link(file: &str, line: usize, target: &str) -> Link {
+        let source = Arc::new(FileLoc::System(file.parse().expect("Valid path")));
+        Link::new(source, Position { line, column: 1 }, target)
+    }
+
+    #[test]
+    fn invert() {
+        let links = [
+            link("README.md", 1, "docs/setup.md#install"),
+            link("README.md", 2, "docs/setup.md"),
+            link("docs/faq.md", 3, "setup.md#install"),
+            link("docs/setup.md", 4, "#usage"),
+            link("docs/faq.md", 5, "https://example.org/a#b"),
+        ];
+        let entries = index(&links);
+        let targets: Vec<_> = entries.iter().map(Entry::target).collect();
+        assert_eq!(
+            targets,
+            vec![
+                "docs/setup.md",
+                "docs/setup.md#install",
+                "docs/setup.md#usage",
+                "https://example.org/a#b",
+            ]
+        );
+        let install_lines: Vec<_> = entries[1].sources.iter().map(|src| src.pos.line).collect();
+        assert_eq!(install_lines, vec![1, 3]);
+        assert_eq!(entries[2].sources[0].to_string(), "docs/setup.md:4:1");
+    }
+}
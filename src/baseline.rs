@@ -0,0 +1,223 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A baseline of known links and broken links,
+//! so that later runs only report new ones.
+//!
+//! Items are matched by a [`Fingerprint`] that contains no line numbers,
+//! so unrelated edits to a file do not invalidate its baseline.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+
+use async_std::fs;
+use async_std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::graph::document_id;
+use crate::link::{FileLoc, Link};
+use crate::validate::{BrokenLink, Reason};
+
+/// The lines of the scanned markup files, by file,
+/// as collected by [`crate::extract_with_source_lines`].
+pub type SourceLines = HashMap<Arc<FileLoc>, Vec<String>>;
+
+/// What identifies a link or a broken link across runs.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// The normalized path of the file the link was found in
+    pub file: String,
+    /// The target of the link
+    pub target: String,
+    /// The line the link was found on,
+    /// trimmed and with all whitespace collapsed to single spaces
+    pub context: String,
+    /// Why the link is broken; `None` for plain links
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<Reason>,
+}
+
+impl Fingerprint {
+    /// The fingerprint of a plain link, taking its context from `source_lines`.
+    #[must_use]
+    pub fn of_link(link: &Link, source_lines: &SourceLines) -> Self {
+        let context = source_lines
+            .get(&link.source.file)
+            .and_then(|lines| lines.get(link.source.pos.line.checked_sub(1)?))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+        Self {
+            file: document_id(&link.source.file),
+            target: link.target.to_string(),
+            context,
+            reason: None,
+        }
+    }
+
+    /// The fingerprint of a broken link, which includes why it is broken.
+    #[must_use]
+    pub fn of_broken(broken: &BrokenLink, source_lines: &SourceLines) -> Self {
+        Self {
+            reason: Some(broken.reason),
+            ..Self::of_link(&broken.link, source_lines)
+        }
+    }
+}
+
+/// A multi-set of fingerprints;
+/// identical items are counted,
+/// so adding a second copy of a known link is reported as new.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Baseline {
+    counts: HashMap<Fingerprint, usize>,
+}
+
+impl Baseline {
+    /// Records all of `links` and `broken_links`.
+    #[must_use]
+    pub fn new(links: &[Link], broken_links: &[BrokenLink], source_lines: &SourceLines) -> Self {
+        let mut baseline = Self::default();
+        for link in links {
+            baseline.record(Fingerprint::of_link(link, source_lines));
+        }
+        for broken in broken_links {
+            baseline.record(Fingerprint::of_broken(broken, source_lines));
+        }
+        baseline
+    }
+
+    /// Adds one occurrence of `fingerprint`.
+    pub fn record(&mut self, fingerprint: Fingerprint) {
+        *self.counts.entry(fingerprint).or_default() += 1;
+    }
+
+    /// Removes one occurrence of `fingerprint`,
+    /// returning `true` if there was one left to remove,
+    /// i.e. if the item is already known.
+    pub fn take(&mut self, fingerprint: &Fingerprint) -> bool {
+        match self.counts.get_mut(fingerprint) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The links of `links` that are not in the baseline.
+    pub fn new_links(&mut self, links: &[Link], source_lines: &SourceLines) -> Vec<Link> {
+        links
+            .iter()
+            .filter(|link| !self.take(&Fingerprint::of_link(link, source_lines)))
+            .cloned()
+            .collect()
+    }
+
+    /// The broken links of `broken_links` that are not in the baseline.
+    pub fn new_broken_links(
+        &mut self,
+        broken_links: &[BrokenLink],
+        source_lines: &SourceLines,
+    ) -> Vec<BrokenLink> {
+        broken_links
+            .iter()
+            .filter(|broken| !self.take(&Fingerprint::of_broken(broken, source_lines)))
+            .cloned()
+            .collect()
+    }
+
+    /// Parses a baseline from a JSON array of fingerprints.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON is malformed.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let fingerprints: Vec<Fingerprint> = serde_json::from_str(json)?;
+        let mut baseline = Self::default();
+        for fingerprint in fingerprints {
+            baseline.record(fingerprint);
+        }
+        Ok(baseline)
+    }
+
+    /// Serializes the baseline to a sorted JSON array of fingerprints,
+    /// repeating those recorded more than once,
+    /// so it diffs well under version control.
+    ///
+    /// # Errors
+    ///
+    /// If serialization fails.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let mut fingerprints: Vec<&Fingerprint> = self
+            .counts
+            .iter()
+            .flat_map(|(fingerprint, count)| std::iter::repeat_n(fingerprint, *count))
+            .collect();
+        fingerprints.sort();
+        serde_json::to_string_pretty(&fingerprints)
+    }
+
+    /// Loads a baseline from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be read or parsed.
+    pub async fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path).await?;
+        Ok(Self::from_json(&json)?)
+    }
+
+    /// Saves the baseline to a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be written.
+    pub async fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_json()?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::Position;
+
+    #[test]
+    fn survives_line_shifts() {
+        let readme = Arc::new(FileLoc::System("README.md".parse().expect("Valid path")));
+        let link = |line: usize, target: &str| {
+            Link::new(readme.clone(), Position { line, column: 1 }, target)
+        };
+        let before: SourceLines = HashMap::from([(
+            readme.clone(),
+            vec!["See [a](a.md)  and".to_owned(), "[b](b.md)".to_owned()],
+        )]);
+        let after: SourceLines = HashMap::from([(
+            readme.clone(),
+            vec![
+                "# Title".to_owned(),
+                "See [a](a.md) and".to_owned(),
+                "[b](b.md)".to_owned(),
+                "[b](b.md)".to_owned(),
+                "[c](c.md)".to_owned(),
+            ],
+        )]);
+        let baseline = Baseline::new(&[link(1, "a.md"), link(2, "b.md")], &[], &before);
+        let mut loaded =
+            Baseline::from_json(&baseline.to_json().expect("Serializable")).expect("Valid JSON");
+        assert_eq!(loaded, baseline);
+        let new_links = loaded.new_links(
+            &[
+                link(2, "a.md"),
+                link(3, "b.md"),
+                link(4, "b.md"),
+                link(5, "c.md"),
+            ],
+            &after,
+        );
+        let reported_lines: Vec<_> = new_links.iter().map(|link| link.source.pos.line).collect();
+        assert_eq!(reported_lines, vec![4, 5]);
+    }
+}
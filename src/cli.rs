@@ -13,11 +13,17 @@
 //! (Markup Link Checker).
 
 use crate::BoxResult;
-use crate::config::{Extractor as ExtractorConfig, Tool as ToolConfig};
+use crate::config::{self, Tool as ToolConfig};
+use crate::encoding;
 use crate::ignore_link;
+use crate::link::TargetKind;
+use crate::link_filter::{LinkFilter, Locality, Rule};
+use crate::markup::{self, TypeOverride};
 use crate::result;
+use crate::source_code::Language;
+use crate::url_mapping::UrlMapping;
 use async_std::io::BufReadExt;
-use clap::builder::ValueParser;
+use clap::builder::{PossibleValuesParser, ValueParser};
 use clap::command;
 use clap::value_parser;
 use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint};
@@ -26,6 +32,7 @@ use cli_utils::path_buf::PathBuf;
 use const_format::formatcp;
 use futures::StreamExt;
 use futures::pin_mut;
+use regex::Regex;
 use std::collections::HashSet;
 use std::sync::LazyLock;
 use std::{env, io};
@@ -44,14 +51,56 @@ pub const A_L_ANCHORS: &str = "anchors";
 pub const A_S_ANCHORS: char = 'a';
 pub const A_L_IGNORE_LINKS: &str = "ignore-links";
 pub const A_S_IGNORE_LINKS: char = 'i';
+pub const A_L_IGNORE_ANCHORS: &str = "ignore-anchors";
+pub const A_L_IGNORE_FILES: &str = "ignore-files";
+pub const A_L_IGNORED: &str = "ignored";
+pub const A_L_VALIDATE: &str = "validate";
+pub const A_L_REMOTE_CACHE: &str = "remote-cache";
+pub const A_L_BASELINE: &str = "baseline";
+pub const A_L_WRITE_BASELINE: &str = "write-baseline";
+pub const A_L_DIFF: &str = "diff";
+pub const A_L_ORPHANS: &str = "orphans";
+pub const A_L_ENTRY_POINTS: &str = "entry-points";
+pub const A_L_BACKLINKS: &str = "backlinks";
+pub const A_L_SUMMARY: &str = "summary";
+pub const A_L_GRAPH: &str = "graph";
+pub const A_L_GRAPH_FORMAT: &str = "graph-format";
+pub const A_L_INCLUDE_REGEX: &str = "include-regex";
+pub const A_L_EXCLUDE_REGEX: &str = "exclude-regex";
+pub const A_L_INCLUDE_KINDS: &str = "include-kinds";
+pub const A_L_EXCLUDE_KINDS: &str = "exclude-kinds";
+pub const A_L_INCLUDE_HOSTS: &str = "include-hosts";
+pub const A_L_EXCLUDE_HOSTS: &str = "exclude-hosts";
+pub const A_L_LOCALITY: &str = "locality";
+pub const A_L_SITE_ROOT: &str = "site-root";
+pub const A_L_CANONICAL: &str = "canonical";
+pub const A_L_NO_CANONICAL: &str = "no-canonical";
+pub const A_L_URL_MAPPINGS: &str = "url-mappings";
+pub const A_L_CODE_LANGUAGES: &str = "code-languages";
+pub const A_L_MARKUP_TYPE: &str = "markup-type";
+pub const A_S_MARKUP_TYPE: char = 'm';
+pub const A_L_GIT_REV: &str = "rev";
+pub const A_S_GIT_REV: char = 'r';
+pub const A_L_TYPE_OVERRIDES: &str = "type-overrides";
+pub const A_S_TYPE_OVERRIDES: char = 't';
+pub const A_L_SNIFF: &str = "sniff";
+pub const A_L_NO_SNIFF: &str = "no-sniff";
+pub const A_L_DEFAULT_ENCODING: &str = "default-encoding";
 pub const A_L_LINKS_FILE: &str = "links-file";
 pub const A_S_LINKS_FILE: char = 'P';
 pub const A_L_RESULT_FORMAT: &str = "result-format";
 pub const A_S_RESULT_FORMAT: char = 'F';
 pub const A_L_RESULT_EXTENDED: &str = "result-extended";
 pub const A_S_RESULT_EXTENDED: char = 'E';
+pub const A_L_NO_RESULT_EXTENDED: &str = "no-result-extended";
 pub const A_L_RESULT_FLUSH: &str = "result-flush";
 pub const A_S_RESULT_FLUSH: char = 'f';
+pub const A_L_NO_RESULT_FLUSH: &str = "no-result-flush";
+pub const A_L_GROUP_BY_TARGET: &str = "group-by-target";
+pub const A_L_NO_GROUP_BY_TARGET: &str = "no-group-by-target";
+pub const A_L_CONFIG: &str = "config";
+pub const A_S_CONFIG: char = 'c';
+pub const A_L_PRINT_CONFIG: &str = "print-config";
 pub const HH_VERBOSITY: &str = "Verbosity";
 pub const HH_ADVANCED: &str = "Advanced";
 
@@ -88,12 +137,16 @@ and only shows log output on stderr.",
 pub fn arg_markup_files() -> Arg {
     Arg::new(A_N_MARKUP_FILES)
         .help("The markup files to extract links and/or anchors from")
+        .long_help(formatcp!(
+            "The markup files to extract links and/or anchors from. \
+Use '-' to read from stdin (requires -{A_S_MARKUP_TYPE},--{A_L_MARKUP_TYPE}), \
+and HTTP(S) URLs to download served files."
+        ))
         .num_args(1..)
         .value_parser(value_parser!(PathBuf))
         .value_name("MARKUP_FILE")
         .value_hint(ValueHint::DirPath)
         .action(ArgAction::Append)
-        .required_unless_present_any([A_L_VERSION, A_L_MARKUP_FILES_LIST])
         .conflicts_with(A_L_MARKUP_FILES_LIST)
 }
 
@@ -110,7 +163,6 @@ to extract links and/or anchors from; one per line.",
         .long(A_L_MARKUP_FILES_LIST)
         .value_parser(value_parser!(PathBuf))
         .action(ArgAction::Set)
-        .required_unless_present_any([A_L_VERSION, A_N_MARKUP_FILES])
         .conflicts_with(A_N_MARKUP_FILES)
 }
 
@@ -119,13 +171,12 @@ pub fn arg_no_links() -> Arg {
     Arg::new(A_L_NO_LINKS)
         .help_heading(HH_ADVANCED)
         .help("Do not extract links")
-        .long_help(
+        .long_help(formatcp!(
             "Do not extract links. \
-See -{A_S_ANCHORS},--{A_L_ANCHORS}.",
-        )
+See -{A_S_ANCHORS},--{A_L_ANCHORS}."
+        ))
         .short(A_S_NO_LINKS)
         .long(A_L_NO_LINKS)
-        .requires(A_L_ANCHORS)
         .action(ArgAction::SetTrue)
 }
 
@@ -143,7 +194,7 @@ and optionally the file to store them to",
         .long(A_L_ANCHORS)
         .value_parser(value_parser!(PathBuf))
         .action(ArgAction::Set)
-        .default_value(cli_utils::STREAM_PATH_STR)
+        .default_missing_value(cli_utils::STREAM_PATH_STR)
 }
 
 #[must_use]
@@ -163,6 +214,597 @@ which will not be extracted; separated by white-space.",
         .action(ArgAction::Append)
 }
 
+#[must_use]
+pub fn arg_ignore_anchors() -> Arg {
+    Arg::new(A_L_IGNORE_ANCHORS)
+        .help_heading(HH_ADVANCED)
+        .help("List of anchor names which will not be extracted; space separated")
+        .long_help(
+            "One or more wildcard-patterns/globs, matching names of anchors \
+which will not be extracted; separated by white-space.",
+        )
+        .num_args(1..)
+        .value_parser(ValueParser::new(ignore_link::parse))
+        .value_name("GLOB")
+        .long(A_L_IGNORE_ANCHORS)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_ignore_files() -> Arg {
+    Arg::new(A_L_IGNORE_FILES)
+        .help_heading(HH_ADVANCED)
+        .help("List of markup files which will not be scanned; space separated")
+        .long_help(
+            "One or more wildcard-patterns/globs, matching paths of markup files \
+which will not be scanned for links and anchors; separated by white-space.",
+        )
+        .num_args(1..)
+        .value_parser(ValueParser::new(ignore_link::parse))
+        .value_name("GLOB")
+        .long(A_L_IGNORE_FILES)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_ignored() -> Arg {
+    Arg::new(A_L_IGNORED)
+        .help_heading(HH_ADVANCED)
+        .help(
+            "Report what each ignore rule has ignored, \
+and optionally the file to store that report to",
+        )
+        .long_help(
+            "Report what each ignore rule \
+(--ignore-links, --ignore-anchors and --ignore-files) has ignored, \
+in the result format, and optionally the file to store that report to. \
+Rules that never matched anything are additionally logged as warnings.",
+        )
+        .num_args(0..=1)
+        .value_name("FILE")
+        .long(A_L_IGNORED)
+        .value_parser(value_parser!(PathBuf))
+        .action(ArgAction::Set)
+        .default_missing_value(cli_utils::STREAM_PATH_STR)
+}
+
+#[must_use]
+pub fn arg_validate() -> Arg {
+    Arg::new(A_L_VALIDATE)
+        .help_heading(HH_ADVANCED)
+        .help(
+            "Check local links and their fragments offline, \
+and optionally the file to store the broken ones to",
+        )
+        .long_help(
+            "Check local links offline: \
+Links to the local file-system (relative, absolute and file:// ones) \
+have to point to an existing file or directory, \
+and their fragment has to match an anchor of the linked file, \
+if that file was scanned in the same run. \
+Each broken link is reported with a reason code \
+(invalid-target, missing-file or missing-anchor), \
+in the result format, and optionally to the given file. \
+If any broken link was found, mle exits with an error. \
+Remote links are never checked; the network is never accessed.",
+        )
+        .num_args(0..=1)
+        .value_name("FILE")
+        .long(A_L_VALIDATE)
+        .value_parser(value_parser!(PathBuf))
+        .action(ArgAction::Set)
+        .default_missing_value(cli_utils::STREAM_PATH_STR)
+}
+
+#[must_use]
+pub fn arg_remote_cache() -> Arg {
+    Arg::new(A_L_REMOTE_CACHE)
+        .help_heading(HH_ADVANCED)
+        .help("A JSON file caching the anchors of remote pages")
+        .long_help(
+            "A JSON file caching the anchors of remote pages, \
+as filled by link checkers that use this tool as a library. \
+It is loaded before and saved after the run. \
+In the extended result formats, fragments of links to cached pages \
+are annotated with whether the page has a matching anchor. \
+Remote pages are never fetched.",
+        )
+        .num_args(1)
+        .value_hint(ValueHint::FilePath)
+        .value_name("FILE")
+        .value_parser(value_parser!(PathBuf))
+        .long(A_L_REMOTE_CACHE)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_baseline() -> Arg {
+    Arg::new(A_L_BASELINE)
+        .help_heading(HH_ADVANCED)
+        .help("Only report links and broken links that are not in this baseline file")
+        .long_help(
+            "A JSON file of known links and broken links, \
+as written with --write-baseline. \
+Only links and broken links that are not in it are reported. \
+Items are matched by the file they are in, their target \
+and the text of the line they are on, \
+but not by line number, \
+so unrelated edits do not invalidate the baseline.",
+        )
+        .num_args(1)
+        .value_hint(ValueHint::FilePath)
+        .value_name("FILE")
+        .value_parser(value_parser!(PathBuf))
+        .long(A_L_BASELINE)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_write_baseline() -> Arg {
+    Arg::new(A_L_WRITE_BASELINE)
+        .help_heading(HH_ADVANCED)
+        .help("Record all links and broken links of this run to a baseline file")
+        .long_help(
+            "Record all the links of this run to a JSON file, \
+for use with --baseline in later runs. \
+Broken links are recorded too, if --validate is given; \
+the run then does not fail because of them.",
+        )
+        .num_args(1)
+        .value_hint(ValueHint::FilePath)
+        .value_name("FILE")
+        .value_parser(value_parser!(PathBuf))
+        .long(A_L_WRITE_BASELINE)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_diff() -> Arg {
+    Arg::new(A_L_DIFF)
+        .help_heading(HH_ADVANCED)
+        .help("Report the links and anchors added, removed or moved between OLD and NEW")
+        .long_help(formatcp!(
+            "Report the links and anchors that were added, removed \
+or moved to an other file between OLD and NEW, \
+instead of the links and anchors themselves. \
+Each of the two is either a JSON result of this tool \
+(links or anchors, simple or extended), \
+or a directory, in which case the given markup files \
+are scanned relative to it. \
+Links and anchors that merely shifted within their file \
+are not reported. \
+Link changes are written to where links would be written to, \
+anchor changes to where anchors would be written to, \
+and the graph of the changed links to where --{A_L_GRAPH} would be written to.",
+        ))
+        .num_args(2)
+        .value_names(["OLD", "NEW"])
+        .value_hint(ValueHint::AnyPath)
+        .value_parser(value_parser!(PathBuf))
+        .long(A_L_DIFF)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_orphans() -> Arg {
+    Arg::new(A_L_ORPHANS)
+        .help_heading(HH_ADVANCED)
+        .help(
+            "Report scanned pages no other scanned page links to, \
+and optionally the file to store that report to",
+        )
+        .long_help(
+            "Report the scanned markup files that no other scanned file links to, \
+and those that can not be reached by following links from the entry points \
+(see --entry-points), in the result format, \
+and optionally the file to store that report to.",
+        )
+        .num_args(0..=1)
+        .value_name("FILE")
+        .long(A_L_ORPHANS)
+        .value_parser(value_parser!(PathBuf))
+        .action(ArgAction::Set)
+        .default_missing_value(cli_utils::STREAM_PATH_STR)
+}
+
+#[must_use]
+pub fn arg_entry_points() -> Arg {
+    Arg::new(A_L_ENTRY_POINTS)
+        .help_heading(HH_ADVANCED)
+        .help("Globs for the scanned markup files that are entry points")
+        .long_help(
+            "Globs for the paths of the scanned markup files that are entry points, \
+from which all other pages should be reachable by following links; \
+used by --orphans. \
+[default: README.md index.html index.md]",
+        )
+        .num_args(1..)
+        .value_parser(ValueParser::new(ignore_link::parse))
+        .value_name("GLOB")
+        .long(A_L_ENTRY_POINTS)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_backlinks() -> Arg {
+    Arg::new(A_L_BACKLINKS)
+        .help_heading(HH_ADVANCED)
+        .help(
+            "Write an index of who links to each file and anchor, \
+and optionally the file to store it to",
+        )
+        .long_help(
+            "Write the backlinks index: \
+For each linked file and each linked anchor, \
+the locations of all the links to it, \
+in the result format, and optionally to the given file. \
+Useful to find what needs to be adjusted before renaming a file or heading.",
+        )
+        .num_args(0..=1)
+        .value_name("FILE")
+        .long(A_L_BACKLINKS)
+        .value_parser(value_parser!(PathBuf))
+        .action(ArgAction::Set)
+        .default_missing_value(cli_utils::STREAM_PATH_STR)
+}
+
+#[must_use]
+pub fn arg_summary() -> Arg {
+    Arg::new(A_L_SUMMARY)
+        .help_heading(HH_ADVANCED)
+        .help("Write summary statistics, and optionally the file to store them to")
+        .long_help(
+            "Write summary statistics: \
+links per file, per target kind and per host, anchors per type, \
+the most linked external domains, \
+and the number of invalid targets and of errors. \
+Supports the text, Markdown and JSON result formats; \
+optionally stored to the given file.",
+        )
+        .num_args(0..=1)
+        .value_name("FILE")
+        .long(A_L_SUMMARY)
+        .value_parser(value_parser!(PathBuf))
+        .action(ArgAction::Set)
+        .default_missing_value(cli_utils::STREAM_PATH_STR)
+}
+
+#[must_use]
+pub fn arg_graph() -> Arg {
+    Arg::new(A_L_GRAPH)
+        .help_heading(HH_ADVANCED)
+        .help("Write the document-level link graph, and optionally the file to store it to")
+        .long_help(formatcp!(
+            "Write the document-level graph of the links: \
+nodes are the markup files and the external hosts, \
+edges are all the links from one node to an other, \
+with their count and the fragments they point to. \
+The format is chosen with --{A_L_GRAPH_FORMAT}; \
+optionally stored to the given file."
+        ))
+        .num_args(0..=1)
+        .value_name("FILE")
+        .long(A_L_GRAPH)
+        .value_parser(value_parser!(PathBuf))
+        .action(ArgAction::Set)
+        .default_missing_value(cli_utils::STREAM_PATH_STR)
+}
+
+#[must_use]
+pub fn arg_graph_format() -> Arg {
+    Arg::new(A_L_GRAPH_FORMAT)
+        .help_heading(HH_ADVANCED)
+        .help("Data format of the link graph")
+        .long_help(formatcp!(
+            "Data format of the link graph written with --{A_L_GRAPH}: \
+GraphViz DOT, GraphML, or JSON nodes and edges."
+        ))
+        .num_args(1)
+        .value_parser(value_parser!(result::GraphType))
+        .value_name("FORMAT")
+        .long(A_L_GRAPH_FORMAT)
+        .action(ArgAction::Set)
+}
+
+fn parse_regex(regex: &str) -> Result<Regex, String> {
+    Regex::new(regex).map_err(|err| err.to_string())
+}
+
+fn arg_link_regex(name: &'static str, verb: &'static str) -> Arg {
+    Arg::new(name)
+        .help_heading(HH_ADVANCED)
+        .help(format!(
+            "Regular expressions for links to {verb}; space separated"
+        ))
+        .long_help(format!(
+            "One or more regular expressions, searched for in the link target; \
+links matching any of them are {verb}d. Separated by white-space."
+        ))
+        .num_args(1..)
+        .value_parser(ValueParser::new(parse_regex))
+        .value_name("REGEX")
+        .long(name)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_include_regex() -> Arg {
+    arg_link_regex(A_L_INCLUDE_REGEX, "include")
+}
+
+#[must_use]
+pub fn arg_exclude_regex() -> Arg {
+    arg_link_regex(A_L_EXCLUDE_REGEX, "exclude")
+}
+
+fn arg_link_kinds(name: &'static str, verb: &'static str) -> Arg {
+    Arg::new(name)
+        .help_heading(HH_ADVANCED)
+        .help(format!("Kinds of link targets to {verb}; space separated"))
+        .long_help(format!(
+            "One or more kinds of link targets (e.g. 'http' or 'email'); \
+links of any of these kinds are {verb}d. Separated by white-space."
+        ))
+        .num_args(1..)
+        .value_parser(value_parser!(TargetKind))
+        .value_name("KIND")
+        .long(name)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_include_kinds() -> Arg {
+    arg_link_kinds(A_L_INCLUDE_KINDS, "include")
+}
+
+#[must_use]
+pub fn arg_exclude_kinds() -> Arg {
+    arg_link_kinds(A_L_EXCLUDE_KINDS, "exclude")
+}
+
+fn arg_link_hosts(name: &'static str, verb: &'static str) -> Arg {
+    Arg::new(name)
+        .help_heading(HH_ADVANCED)
+        .help(format!("Hosts of links to {verb}; space separated"))
+        .long_help(format!(
+            "One or more wildcard-patterns/globs (e.g. '*.example.com'), \
+matched against the host of URLs and the domain of e-mail addresses; \
+links with a matching host are {verb}d. Separated by white-space."
+        ))
+        .num_args(1..)
+        .value_parser(ValueParser::new(ignore_link::parse))
+        .value_name("GLOB")
+        .long(name)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_include_hosts() -> Arg {
+    arg_link_hosts(A_L_INCLUDE_HOSTS, "include")
+}
+
+#[must_use]
+pub fn arg_exclude_hosts() -> Arg {
+    arg_link_hosts(A_L_EXCLUDE_HOSTS, "exclude")
+}
+
+#[must_use]
+pub fn arg_locality() -> Arg {
+    Arg::new(A_L_LOCALITY)
+        .help_heading(HH_ADVANCED)
+        .help("Only extract either local or remote links")
+        .num_args(1)
+        .value_parser(value_parser!(Locality))
+        .value_name("LOCALITY")
+        .long(A_L_LOCALITY)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_site_root() -> Arg {
+    Arg::new(A_L_SITE_ROOT)
+        .help_heading(HH_ADVANCED)
+        .help("The directory to resolve root-relative links against")
+        .long_help(
+            "The directory to resolve root-relative link targets \
+(e.g. '/docs/setup.md') against, \
+instead of against the root of the file-system; \
+usually the root of the repository or of the web-site sources.",
+        )
+        .num_args(1)
+        .value_hint(ValueHint::DirPath)
+        .value_name("DIR")
+        .value_parser(value_parser!(PathBuf))
+        .long(A_L_SITE_ROOT)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_canonical() -> Arg {
+    Arg::new(A_L_CANONICAL)
+        .help_heading(HH_ADVANCED)
+        .help("Output link targets in their canonical form")
+        .long_help(
+            "Output link targets in their canonical form: \
+relative paths are made absolute \
+(using the working directory for relative markup files), \
+and './' and '../' parts are resolved.",
+        )
+        .long(A_L_CANONICAL)
+        .overrides_with(A_L_NO_CANONICAL)
+        .action(ArgAction::SetTrue)
+}
+
+#[must_use]
+pub fn arg_url_mappings() -> Arg {
+    Arg::new(A_L_URL_MAPPINGS)
+        .help_heading(HH_ADVANCED)
+        .help("Local directories and the URLs they are published under; space separated")
+        .long_help(
+            "One or more mappings of a local directory to the URL it is published under, \
+in the form PATH=URL[,LOCAL_EXT=PUBLIC_EXT...] \
+(e.g. 'site=https://docs.example.org/,md=html'); separated by white-space. \
+The extended result output then shows the public URL of local files \
+and the local file of public URLs.",
+        )
+        .num_args(1..)
+        .value_parser(value_parser!(UrlMapping))
+        .value_name("PATH=URL")
+        .long(A_L_URL_MAPPINGS)
+        .action(ArgAction::Append)
+}
+
+static CODE_LANGUAGE_NAMES: LazyLock<Vec<String>> = LazyLock::new(Language::builtin_names);
+
+#[must_use]
+pub fn arg_code_languages() -> Arg {
+    Arg::new(A_L_CODE_LANGUAGES)
+        .help_heading(HH_ADVANCED)
+        .help("Languages whose comments are scanned for URLs in source-code files")
+        .long_help(
+            "The (programming-) languages whose comments are scanned for URLs, \
+in files of markup type 'code'; separated by white-space. \
+Only files in these languages are detected as source-code; \
+by default, none are.",
+        )
+        .num_args(1..)
+        .value_parser(PossibleValuesParser::new(
+            CODE_LANGUAGE_NAMES.iter().map(String::as_str),
+        ))
+        .value_name("LANGUAGE")
+        .long(A_L_CODE_LANGUAGES)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_markup_type() -> Arg {
+    Arg::new(A_L_MARKUP_TYPE)
+        .help_heading(HH_ADVANCED)
+        .help("The markup type of all input files; required for stdin")
+        .long_help(
+            "The markup type of all input files, \
+instead of detecting it for each file. \
+This is required when reading from stdin.",
+        )
+        .num_args(1)
+        .value_parser(value_parser!(markup::Type))
+        .value_name("TYPE")
+        .short(A_S_MARKUP_TYPE)
+        .long(A_L_MARKUP_TYPE)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_git_rev() -> Arg {
+    Arg::new(A_L_GIT_REV)
+        .help_heading(HH_ADVANCED)
+        .help("Read the markup files as they are in this git revision")
+        .long_help(
+            "Read the markup files as they are stored \
+in this revision (e.g. a tag, branch or commit hash) \
+of the local git repository they are located in, \
+instead of from the working tree. \
+Found links and anchors are then located as REV:PATH. \
+Archives and compressed files can not be read this way.",
+        )
+        .num_args(1)
+        .value_name("REV")
+        .short(A_S_GIT_REV)
+        .long(A_L_GIT_REV)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_type_overrides() -> Arg {
+    Arg::new(A_L_TYPE_OVERRIDES)
+        .help_heading(HH_ADVANCED)
+        .help("Markup types to use for files matching a glob; space separated")
+        .long_help(
+            "One or more mappings of a wildcard-pattern/glob to a markup type, \
+in the form GLOB=TYPE (e.g. '*/README=md' or '*.mdx.txt=mdx'); \
+separated by white-space. \
+The glob is matched against both the whole path and the file name. \
+The first matching mapping takes precedence over the file extension.",
+        )
+        .num_args(1..)
+        .value_parser(value_parser!(TypeOverride))
+        .value_name("GLOB=TYPE")
+        .short(A_S_TYPE_OVERRIDES)
+        .long(A_L_TYPE_OVERRIDES)
+        .action(ArgAction::Append)
+}
+
+#[must_use]
+pub fn arg_sniff() -> Arg {
+    Arg::new(A_L_SNIFF)
+        .help_heading(HH_ADVANCED)
+        .help("Guess the markup type from the content, if the file extension is unknown")
+        .long_help(
+            "If the markup type of a file can not be derived \
+from its file extension (or a type override), \
+guess it from the start of its content: \
+a doctype or <html> tag means HTML, \
+headings or links mean Markdown.",
+        )
+        .long(A_L_SNIFF)
+        .overrides_with(A_L_NO_SNIFF)
+        .action(ArgAction::SetTrue)
+}
+
+#[must_use]
+pub fn arg_default_encoding() -> Arg {
+    Arg::new(A_L_DEFAULT_ENCODING)
+        .help_heading(HH_ADVANCED)
+        .help("The character encoding of files that are not UTF-8 and do not declare one")
+        .long_help(
+            "The character encoding (e.g. windows-1252 or Shift_JIS) \
+to decode files with that are not valid UTF-8 \
+and declare their encoding neither with a byte order mark \
+nor with a <meta charset> or XML declaration.",
+        )
+        .num_args(1)
+        .value_parser(ValueParser::new(encoding::parse_label))
+        .value_name("LABEL")
+        .long(A_L_DEFAULT_ENCODING)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_config() -> Arg {
+    Arg::new(A_L_CONFIG)
+        .help("The configuration file to use")
+        .long_help(formatcp!(
+            "The TOML configuration file to use. \
+By default, '{}' is searched for in the working directory \
+and its ancestors. \
+Settings given on the command line take precedence \
+over those in the file.",
+            config::FILE_NAME
+        ))
+        .num_args(1)
+        .value_parser(value_parser!(PathBuf))
+        .value_name("FILE")
+        .value_hint(ValueHint::FilePath)
+        .short(A_S_CONFIG)
+        .long(A_L_CONFIG)
+        .action(ArgAction::Set)
+}
+
+#[must_use]
+pub fn arg_print_config() -> Arg {
+    Arg::new(A_L_PRINT_CONFIG)
+        .help("Print the effective configuration as TOML and exit")
+        .long_help(formatcp!(
+            "Print the effective configuration - \
+the configuration file merged with the command line arguments - \
+as TOML and exit. \
+This output may serve as a starting point for a '{}' file.",
+            config::FILE_NAME
+        ))
+        .long(A_L_PRINT_CONFIG)
+        .action(ArgAction::SetTrue)
+}
+
 #[must_use]
 pub fn arg_links_file() -> Arg {
     Arg::new(A_L_LINKS_FILE)
@@ -196,6 +838,7 @@ pub fn arg_result_extended() -> Arg {
         .help("Output additional properties per link/anchor")
         .short(A_S_RESULT_EXTENDED)
         .long(A_L_RESULT_EXTENDED)
+        .overrides_with(A_L_NO_RESULT_EXTENDED)
         .action(ArgAction::SetTrue)
 }
 
@@ -210,6 +853,42 @@ Not all output formats support this.",
         )
         .short(A_S_RESULT_FLUSH)
         .long(A_L_RESULT_FLUSH)
+        .overrides_with(A_L_NO_RESULT_FLUSH)
+        .action(ArgAction::SetTrue)
+}
+
+#[must_use]
+pub fn arg_group_by_target() -> Arg {
+    Arg::new(A_L_GROUP_BY_TARGET)
+        .help_heading(HH_ADVANCED)
+        .help("Write each distinct link target only once, with all its sources")
+        .long_help(
+            "Group the links by their canonical target without fragment, \
+writing each distinct target only once, \
+together with all the locations it is linked from \
+and all the fragments linked to. \
+Relative targets are resolved to absolute paths. \
+Useful for link checkers, which then have to check each target only once. \
+Supports the text, Markdown, CSV, TSV and JSON result formats.",
+        )
+        .action(ArgAction::SetTrue)
+        .long(A_L_GROUP_BY_TARGET)
+        .overrides_with(A_L_NO_GROUP_BY_TARGET)
+}
+
+/// The counterpart of the flag `negated`,
+/// to turn off a setting enabled in the configuration file.
+/// Whichever of the two is given last wins.
+#[must_use]
+pub fn arg_negation(long: &'static str, negated: &'static str) -> Arg {
+    Arg::new(long)
+        .help_heading(HH_ADVANCED)
+        .help(format!("Undo --{negated}"))
+        .long_help(format!(
+            "Undo --{negated}, e.g. when it is enabled in the configuration file."
+        ))
+        .long(long)
+        .overrides_with(negated)
         .action(ArgAction::SetTrue)
 }
 
@@ -222,10 +901,48 @@ static ARGS: LazyLock<Vec<Arg>> = LazyLock::new(|| {
         arg_no_links(),
         arg_anchors(),
         arg_ignore_links(),
+        arg_ignore_anchors(),
+        arg_ignore_files(),
+        arg_ignored(),
+        arg_validate(),
+        arg_remote_cache(),
+        arg_baseline(),
+        arg_write_baseline(),
+        arg_diff(),
+        arg_orphans(),
+        arg_entry_points(),
+        arg_backlinks(),
+        arg_summary(),
+        arg_graph(),
+        arg_graph_format(),
+        arg_include_regex(),
+        arg_exclude_regex(),
+        arg_include_kinds(),
+        arg_exclude_kinds(),
+        arg_include_hosts(),
+        arg_exclude_hosts(),
+        arg_locality(),
+        arg_site_root(),
+        arg_canonical(),
+        arg_negation(A_L_NO_CANONICAL, A_L_CANONICAL),
+        arg_url_mappings(),
+        arg_code_languages(),
+        arg_markup_type(),
+        arg_git_rev(),
+        arg_type_overrides(),
+        arg_sniff(),
+        arg_negation(A_L_NO_SNIFF, A_L_SNIFF),
+        arg_default_encoding(),
+        arg_config(),
+        arg_print_config(),
         arg_links_file(),
         arg_result_format(),
         arg_result_extended(),
+        arg_negation(A_L_NO_RESULT_EXTENDED, A_L_RESULT_EXTENDED),
         arg_result_flush(),
+        arg_negation(A_L_NO_RESULT_FLUSH, A_L_RESULT_FLUSH),
+        arg_group_by_target(),
+        arg_negation(A_L_NO_GROUP_BY_TARGET, A_L_GROUP_BY_TARGET),
     ]
 });
 
@@ -312,6 +1029,156 @@ pub fn ignore_links(args: &mut ArgMatches) -> Vec<WildMatch> {
         .collect()
 }
 
+/// Sets the destinations and settings of the reports
+/// that are written in addition to the links and anchors,
+/// as provided through the CLI.
+pub fn reports(args: &mut ArgMatches, conf: &mut ToolConfig) {
+    if let Some(ignored_file) = args.remove_one::<PathBuf>(A_L_IGNORED) {
+        conf.ignored = Some(StreamIdent::from((Some(ignored_file), false)));
+    }
+    if let Some(validation_file) = args.remove_one::<PathBuf>(A_L_VALIDATE) {
+        conf.validation = Some(StreamIdent::from((Some(validation_file), false)));
+    }
+    if let Some(remote_cache) = args.remove_one::<PathBuf>(A_L_REMOTE_CACHE) {
+        conf.remote_cache = Some(remote_cache);
+    }
+    if let Some(baseline) = args.remove_one::<PathBuf>(A_L_BASELINE) {
+        conf.baseline = Some(baseline);
+    }
+    if let Some(baseline) = args.remove_one::<PathBuf>(A_L_WRITE_BASELINE) {
+        conf.write_baseline = Some(baseline);
+    }
+    if let Some(mut sides) = args.remove_many::<PathBuf>(A_L_DIFF) {
+        conf.diff_old = sides.next();
+        conf.diff_new = sides.next();
+    }
+    if let Some(orphans_file) = args.remove_one::<PathBuf>(A_L_ORPHANS) {
+        conf.orphans = Some(StreamIdent::from((Some(orphans_file), false)));
+    }
+    if let Some(globs) = args.remove_many::<WildMatch>(A_L_ENTRY_POINTS) {
+        conf.entry_points = globs.collect();
+    }
+    if let Some(backlinks_file) = args.remove_one::<PathBuf>(A_L_BACKLINKS) {
+        conf.backlinks = Some(StreamIdent::from((Some(backlinks_file), false)));
+    }
+    if let Some(summary_file) = args.remove_one::<PathBuf>(A_L_SUMMARY) {
+        conf.summary = Some(StreamIdent::from((Some(summary_file), false)));
+    }
+    if let Some(graph_file) = args.remove_one::<PathBuf>(A_L_GRAPH) {
+        conf.graph = Some(StreamIdent::from((Some(graph_file), false)));
+    }
+    if let Some(graph_format) = args.remove_one::<result::GraphType>(A_L_GRAPH_FORMAT) {
+        conf.graph_format = graph_format;
+    }
+}
+
+/// Adds the link filter rules provided through the CLI
+/// to the ones already in `filter`.
+///
+/// All the include criteria given on the CLI have to match,
+/// except that matching any one of multiple include regexes suffices.
+/// Matching any one of the exclude criteria excludes a link.
+pub fn link_filter(args: &mut ArgMatches, filter: &mut LinkFilter) {
+    let include = Rule {
+        regex: None,
+        kinds: args
+            .remove_many::<TargetKind>(A_L_INCLUDE_KINDS)
+            .unwrap_or_default()
+            .collect(),
+        hosts: args
+            .remove_many::<WildMatch>(A_L_INCLUDE_HOSTS)
+            .unwrap_or_default()
+            .collect(),
+        locality: args.remove_one::<Locality>(A_L_LOCALITY),
+    };
+    let include_regexes: Vec<Regex> = args
+        .remove_many::<Regex>(A_L_INCLUDE_REGEX)
+        .unwrap_or_default()
+        .collect();
+    if include_regexes.is_empty() {
+        if !include.kinds.is_empty() || !include.hosts.is_empty() || include.locality.is_some() {
+            filter.include.push(include);
+        }
+    } else {
+        filter
+            .include
+            .extend(include_regexes.into_iter().map(|regex| Rule {
+                regex: Some(regex),
+                ..include.clone()
+            }));
+    }
+
+    if let Some(regexes) = args.remove_many::<Regex>(A_L_EXCLUDE_REGEX) {
+        filter.exclude.extend(regexes.map(|regex| Rule {
+            regex: Some(regex),
+            ..Rule::default()
+        }));
+    }
+    if let Some(kinds) = args.remove_many::<TargetKind>(A_L_EXCLUDE_KINDS) {
+        filter.exclude.push(Rule {
+            kinds: kinds.collect(),
+            ..Rule::default()
+        });
+    }
+    if let Some(hosts) = args.remove_many::<WildMatch>(A_L_EXCLUDE_HOSTS) {
+        filter.exclude.push(Rule {
+            hosts: hosts.collect(),
+            ..Rule::default()
+        });
+    }
+}
+
+/// Returns the source-code languages to scan comments of,
+/// as provided through the CLI.
+pub fn code_languages(args: &mut ArgMatches) -> Vec<Language> {
+    args.remove_many::<String>(A_L_CODE_LANGUAGES)
+        .map_or_else(Vec::default, |names| {
+            names.filter_map(|name| Language::builtin(&name)).collect()
+        })
+}
+
+/// Returns the glob to markup type mappings
+/// provided through the CLI.
+pub fn type_overrides(args: &mut ArgMatches) -> Vec<TypeOverride> {
+    args.remove_many::<TypeOverride>(A_L_TYPE_OVERRIDES)
+        .unwrap_or_default()
+        .collect()
+}
+
+/// Prints the configuration as TOML to stdout and exits.
+///
+/// # Errors
+///
+/// If the configuration can not be represented in TOML.
+pub fn print_config_and_exit(conf: &ToolConfig) -> BoxResult<()> {
+    #![allow(clippy::print_stdout)]
+
+    print!("{}", conf.to_toml()?);
+    std::process::exit(0);
+}
+
+/// Loads the configuration file given on the command line,
+/// or the one found in the working directory or its ancestors,
+/// or returns the default configuration if there is none.
+///
+/// # Errors
+///
+/// If fetching the CWD failed,
+/// or the configuration file could not be loaded.
+pub fn load_config_file(args: &mut ArgMatches) -> BoxResult<ToolConfig> {
+    let config_file = match args.remove_one::<PathBuf>(A_L_CONFIG) {
+        Some(path) => Some(std::path::PathBuf::from(path.as_path())),
+        None => ToolConfig::find_file(&env::current_dir()?),
+    };
+    Ok(match config_file {
+        Some(path) => {
+            log::debug!("Loading config file '{}' ...", path.display());
+            ToolConfig::from_file(&path)?
+        }
+        None => ToolConfig::default(),
+    })
+}
+
 pub fn print_version_and_exit(version: &str, quiet: bool) {
     #![allow(clippy::print_stdout)]
 
@@ -322,11 +1189,26 @@ pub fn print_version_and_exit(version: &str, quiet: bool) {
     std::process::exit(0);
 }
 
-/// Parses CLI arguments into our own config structure.
+/// Whether a flag or its negation was given,
+/// see [`arg_negation`].
+fn flag(args: &ArgMatches, enable: &str, disable: &str) -> Option<bool> {
+    if args.get_flag(enable) {
+        Some(true)
+    } else if args.get_flag(disable) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Parses CLI arguments into our own config structure,
+/// on top of the configuration file (see [`load_config_file`]).
 ///
 /// # Errors
 ///
-/// If fetching the CWD failed.
+/// If fetching the CWD failed,
+/// the configuration file could not be loaded,
+/// or no markup files were provided.
 pub async fn parse_args() -> BoxResult<ToolConfig> {
     let mut args = arg_matcher(clap::crate_name!(), &ARGS).get_matches();
 
@@ -336,46 +1218,97 @@ pub async fn parse_args() -> BoxResult<ToolConfig> {
         print_version_and_exit(crate::VERSION, quiet);
     }
 
-    let markup_files = markup_files(&mut args).await?;
-    let links = if args.get_flag(A_L_NO_LINKS) {
-        None
-    } else {
-        Some(StreamIdent::from((
-            args.remove_one::<PathBuf>(A_L_LINKS_FILE)
-                .map(Into::<async_std::path::PathBuf>::into)
-                .map(Into::<cli_utils::path_buf::PathBuf>::into),
-            false,
-        )))
-    };
-    let anchors = if args.get_raw(A_L_ANCHORS).is_none() {
-        None
-    } else {
-        Some(StreamIdent::from((
+    let mut conf = load_config_file(&mut args)?;
+
+    if args.contains_id(A_N_MARKUP_FILES) || args.contains_id(A_L_MARKUP_FILES_LIST) {
+        conf.extractor.markup_files = markup_files(&mut args).await?;
+    }
+    if args.get_flag(A_L_NO_LINKS) {
+        conf.links = None;
+    } else if let Some(links_file) = args.remove_one::<PathBuf>(A_L_LINKS_FILE) {
+        conf.links = Some(StreamIdent::from((Some(links_file), false)));
+    }
+    if args.get_raw(A_L_ANCHORS).is_some() {
+        conf.anchors = Some(StreamIdent::from((
             args.remove_one::<PathBuf>(A_L_ANCHORS)
                 .map(Into::<async_std::path::PathBuf>::into)
                 .map(Into::<cli_utils::path_buf::PathBuf>::into),
             false,
-        )))
-    };
+        )));
+    }
+    conf.extractor.links = conf.links.is_some();
+    conf.extractor.anchors = conf.anchors.is_some();
 
-    let ignore_links: Vec<WildMatch> = ignore_links(&mut args);
-    let result_format = args
-        .remove_one::<result::Type>(A_L_RESULT_FORMAT)
-        .unwrap_or_default();
-    let result_extended = args.get_flag(A_L_RESULT_EXTENDED);
-    let result_flush = args.get_flag(A_L_RESULT_FLUSH);
-
-    Ok(ToolConfig {
-        extractor: ExtractorConfig {
-            markup_files,
-            links: links.is_some(),
-            anchors: anchors.is_some(),
-            ignore_links,
-        },
-        links,
-        anchors,
-        result_format,
-        result_extended,
-        result_flush,
-    })
+    if args.contains_id(A_L_IGNORE_LINKS) {
+        conf.extractor.ignore_links = ignore_links(&mut args);
+    }
+    if let Some(globs) = args.remove_many::<WildMatch>(A_L_IGNORE_ANCHORS) {
+        conf.extractor.ignore_anchors = globs.collect();
+    }
+    if let Some(globs) = args.remove_many::<WildMatch>(A_L_IGNORE_FILES) {
+        conf.extractor.ignore_files = globs.collect();
+    }
+    reports(&mut args, &mut conf);
+    link_filter(&mut args, &mut conf.extractor.link_filter);
+    if let Some(site_root) = args.remove_one::<PathBuf>(A_L_SITE_ROOT) {
+        conf.extractor.site_root = Some(site_root);
+    }
+    if let Some(enabled) = flag(&args, A_L_CANONICAL, A_L_NO_CANONICAL) {
+        conf.extractor.canonical_targets = enabled;
+    }
+    if let Some(url_mappings) = args.remove_many::<UrlMapping>(A_L_URL_MAPPINGS) {
+        conf.extractor.url_mappings = url_mappings.collect();
+    }
+    if args.contains_id(A_L_CODE_LANGUAGES) {
+        conf.extractor.code_languages = code_languages(&mut args);
+    }
+    if let Some(markup_type) = args.remove_one::<markup::Type>(A_L_MARKUP_TYPE) {
+        conf.extractor.markup_type = Some(markup_type);
+    }
+    if let Some(git_rev) = args.remove_one::<String>(A_L_GIT_REV) {
+        conf.extractor.git_rev = Some(git_rev);
+    }
+    if args.contains_id(A_L_TYPE_OVERRIDES) {
+        conf.extractor.type_overrides = type_overrides(&mut args);
+    }
+    if let Some(enabled) = flag(&args, A_L_SNIFF, A_L_NO_SNIFF) {
+        conf.extractor.sniff_content = enabled;
+    }
+    if let Some(default_encoding) = args.remove_one::<String>(A_L_DEFAULT_ENCODING) {
+        conf.extractor.default_encoding = Some(default_encoding);
+    }
+    if let Some(result_format) = args.remove_one::<result::Type>(A_L_RESULT_FORMAT) {
+        conf.result_format = result_format;
+    }
+    if let Some(enabled) = flag(&args, A_L_RESULT_EXTENDED, A_L_NO_RESULT_EXTENDED) {
+        conf.result_extended = enabled;
+    }
+    if let Some(enabled) = flag(&args, A_L_RESULT_FLUSH, A_L_NO_RESULT_FLUSH) {
+        conf.result_flush = enabled;
+    }
+    if let Some(enabled) = flag(&args, A_L_GROUP_BY_TARGET, A_L_NO_GROUP_BY_TARGET) {
+        conf.result_grouped = enabled;
+    }
+
+    if args.get_flag(A_L_PRINT_CONFIG) {
+        print_config_and_exit(&conf)?;
+    }
+    // Diffing two results requires no markup files
+    if conf.extractor.markup_files.is_empty() && conf.diff_old.is_none() {
+        return Err(io::Error::other("No markup files provided").into());
+    }
+    if conf.links.is_none()
+        && conf.anchors.is_none()
+        && conf.validation.is_none()
+        && conf.orphans.is_none()
+        && conf.backlinks.is_none()
+        && conf.summary.is_none()
+        && conf.graph.is_none()
+        && conf.write_baseline.is_none()
+        && conf.ignored.is_none()
+    {
+        return Err(io::Error::other("Neither links nor anchors are to be extracted").into());
+    }
+
+    Ok(conf)
 }
@@ -3,11 +3,23 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::borrow::Cow;
+use std::path::Path;
+
 use cli_utils::{StreamIdent, path_buf::PathBuf};
+use encoding_rs::Encoding;
+use relative_path::RelativePathBuf;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
 use wildmatch::WildMatch;
 
+use crate::anchor::SlugScheme;
+use crate::link_filter::LinkFilter;
+use crate::markup::{self, TypeOverride};
 use crate::result;
+use crate::source_code::Language;
+use crate::url_mapping::UrlMapping;
 
 /// The core config for the link extractor.
 ///
@@ -15,12 +27,15 @@ use crate::result;
 /// of the extractor.
 /// See [`Tool`] for the extended configuration,
 /// used by the CLI invocation.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Extractor {
     /// Markup files to extract links and/or anchors from.
     ///
     /// Use other commands to construct this list,
     /// like `ls` or `git ls-files`.
+    #[serde(with = "paths")]
     pub markup_files: Vec<PathBuf>,
     // pub recursive: bool,
     /// Where to store links to.
@@ -39,7 +54,157 @@ pub struct Extractor {
     /// - `Some(StreamIdent::Path("/path/to/extracted_anchors_file.txt".into(), false))`
     ///   => extract anchors and write them to file `path`.
     pub anchors: bool,
+    #[serde(with = "crate::ignore_link::globs")]
+    pub ignore_links: Vec<WildMatch>,
+    /// Globs for anchor names which will not be extracted
+    #[serde(with = "crate::ignore_link::globs")]
+    pub ignore_anchors: Vec<WildMatch>,
+    /// Globs for markup file paths which will not be scanned at all
+    #[serde(with = "crate::ignore_link::globs")]
+    pub ignore_files: Vec<WildMatch>,
+    /// Include and exclude rules for links,
+    /// applied after [`Self::ignore_links`].
+    pub link_filter: LinkFilter,
+    /// The languages whose comments are scanned for URLs
+    /// in files of markup type [`crate::markup::Type::Code`];
+    /// only files in these languages are detected as such.
+    pub code_languages: Vec<Language>,
+    /// The markup type of all the input files,
+    /// overriding any kind of detection;
+    /// required when reading from stdin.
+    pub markup_type: Option<markup::Type>,
+    /// If set, the markup files are read as they are stored
+    /// in this revision (e.g. a tag or commit) of the local git repository
+    /// they are located in, instead of from the working tree.
+    pub git_rev: Option<String>,
+    /// Markup types to use for files matching a glob,
+    /// taking precedence over the type derived from the file extension.
+    pub type_overrides: Vec<TypeOverride>,
+    /// Whether to guess the markup type from the file content,
+    /// if it can not be derived from the file extension.
+    pub sniff_content: bool,
+    /// The label of the character encoding (e.g. `"windows-1252"`)
+    /// to decode files with that are neither valid UTF-8
+    /// nor declare their own encoding.
+    /// See [`crate::encoding::decode`].
+    #[serde(deserialize_with = "encoding_label::deserialize")]
+    pub default_encoding: Option<String>,
+    /// The rules by which anchors are generated from Markdown titles.
+    pub slug_scheme: SlugScheme,
+    /// If set, relative link targets are resolved against this URL,
+    /// the way an HTML `<base href="...">` element does.
+    pub base_url: Option<Url>,
+    /// If set, root-relative file-system link targets (e.g. `/docs/setup.md`)
+    /// are resolved against this directory (e.g. to `/path/to/site/docs/setup.md`),
+    /// instead of against the root of the file-system.
+    /// A relative directory is resolved against the working directory,
+    /// or when set in a configuration file, against the directory of that file.
+    /// Targets resolved by [`Self::base_url`] are not affected.
+    #[serde(with = "path_opt")]
+    pub site_root: Option<PathBuf>,
+    /// Whether to emit all link targets in their canonical form,
+    /// see [`crate::link::Target::canonical`].
+    /// Relative paths are resolved against the working directory.
+    pub canonical_targets: bool,
+    /// Mappings between local directories and the URLs they are published under,
+    /// used to show the counterpart of each source file and link target
+    /// in the extended result output.
+    /// The first matching mapping is used.
+    pub url_mappings: Vec<UrlMapping>,
+    /// Settings that differ for the markup files matching a glob.
+    /// All matching overrides are applied, in order.
+    pub overrides: Vec<PathOverride>,
+}
+
+/// Settings of [`Extractor`] that apply only to markup files
+/// whose path matches [`Self::glob`].
+/// Unset settings keep the value of the enclosing configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathOverride {
+    /// Matched against the markup file path as given,
+    /// e.g. `vendor/*` or `*.html`
+    #[serde(with = "crate::ignore_link::glob")]
+    pub glob: WildMatch,
+    /// Additional globs for links to be ignored
+    #[serde(default, with = "crate::ignore_link::globs")]
     pub ignore_links: Vec<WildMatch>,
+    /// See [`Extractor::markup_type`]
+    pub markup_type: Option<markup::Type>,
+    /// See [`Extractor::slug_scheme`]
+    pub slug_scheme: Option<SlugScheme>,
+    /// See [`Extractor::base_url`]
+    pub base_url: Option<Url>,
+    /// See [`Extractor::site_root`]
+    #[serde(default, with = "path_opt")]
+    pub site_root: Option<PathBuf>,
+}
+
+impl PathOverride {
+    /// Whether this override applies to the markup file with the given path.
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        self.glob.matches(path.strip_prefix("./").unwrap_or(path))
+    }
+
+    fn apply(&self, conf: &mut Extractor) {
+        conf.ignore_links.extend(self.ignore_links.iter().cloned());
+        if self.markup_type.is_some() {
+            conf.markup_type = self.markup_type;
+        }
+        if let Some(slug_scheme) = self.slug_scheme {
+            conf.slug_scheme = slug_scheme;
+        }
+        if self.base_url.is_some() {
+            conf.base_url.clone_from(&self.base_url);
+        }
+        if self.site_root.is_some() {
+            conf.site_root.clone_from(&self.site_root);
+        }
+    }
+}
+
+impl Extractor {
+    /// The configuration to use for the markup file with the given path,
+    /// with all matching [`Self::overrides`] applied.
+    #[must_use]
+    pub fn for_file(&self, path: &str) -> Cow<'_, Self> {
+        let mut matching = self
+            .overrides
+            .iter()
+            .filter(|path_override| path_override.matches(path))
+            .peekable();
+        if matching.peek().is_none() {
+            return Cow::Borrowed(self);
+        }
+        let mut conf = self.clone();
+        for path_override in matching {
+            path_override.apply(&mut conf);
+        }
+        Cow::Owned(conf)
+    }
+
+    /// The encoding denoted by [`Self::default_encoding`], if it is set.
+    /// Unknown labels are rejected when parsing the CLI arguments
+    /// or the config file.
+    #[must_use]
+    pub fn default_encoding(&self) -> Option<&'static Encoding> {
+        self.default_encoding
+            .as_deref()
+            .and_then(|label| Encoding::for_label(label.as_bytes()))
+    }
+}
+
+/// The name of the configuration file,
+/// which is searched for in the working directory and its ancestors.
+pub const FILE_NAME: &str = "mle.toml";
+
+/// Failed to load a configuration file.
+#[derive(Debug, Error)]
+pub enum FileError {
+    #[error("Failed to read config file '{0}': {1}")]
+    Read(String, std::io::Error),
+    #[error("Failed to parse config file '{0}': {1}")]
+    Parse(String, toml::de::Error),
 }
 
 /// The extended config for the link extractor.
@@ -48,6 +213,7 @@ pub struct Extractor {
 /// of the extractor.
 /// See [`Extractor`] for the library/programmatic invocation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Tool {
     /// The core part of the configuration.
     pub extractor: Extractor,
@@ -58,6 +224,7 @@ pub struct Tool {
     ///   => extract links and write them to stdout,
     /// - `Some(StreamIdent::Path("/path/to/extracted_links_file.txt".into(), false))`
     ///   => extract links and write them to file `path`.
+    #[serde(with = "out_stream")]
     pub links: Option<StreamIdent>,
     /// Where to store anchors to.
     ///
@@ -66,7 +233,67 @@ pub struct Tool {
     ///   => extract anchors and write them to stdout,
     /// - `Some(StreamIdent::Path("/path/to/extracted_anchors_file.txt".into(), false))`
     ///   => extract anchors and write them to file `path`.
+    #[serde(with = "out_stream")]
     pub anchors: Option<StreamIdent>,
+    /// Where to write the report of what each ignore rule has ignored to,
+    /// if anywhere.
+    /// See [`crate::ignored::Report`].
+    #[serde(with = "out_stream")]
+    pub ignored: Option<StreamIdent>,
+    /// Where to write the broken local links to, if anywhere.
+    /// Setting this enables the offline validation of local links and anchors.
+    /// See [`crate::validate::validate`].
+    #[serde(with = "out_stream")]
+    pub validation: Option<StreamIdent>,
+    /// The JSON file to load cached anchors of remote pages from,
+    /// and to save them back to after the run.
+    /// See [`crate::state::RemoteCache`].
+    #[serde(with = "path_opt")]
+    pub remote_cache: Option<PathBuf>,
+    /// The JSON file listing the known links and broken links,
+    /// which are then not reported.
+    /// See [`crate::baseline::Baseline`].
+    #[serde(with = "path_opt")]
+    pub baseline: Option<PathBuf>,
+    /// The JSON file to record all the links and broken links of this run to,
+    /// for use as [`Self::baseline`] in later runs.
+    #[serde(with = "path_opt")]
+    pub write_baseline: Option<PathBuf>,
+    /// The old side of a diff:
+    /// either a JSON result of this tool, or the root of a tree.
+    /// If this and [`Self::diff_new`] are set,
+    /// the changes between the two are reported
+    /// instead of the links and anchors.
+    /// See [`crate::diff`].
+    #[serde(with = "path_opt")]
+    pub diff_old: Option<PathBuf>,
+    /// The new side of a diff; see [`Self::diff_old`].
+    #[serde(with = "path_opt")]
+    pub diff_new: Option<PathBuf>,
+    /// Where to write the report of orphan and unreachable pages to,
+    /// if anywhere.
+    /// See [`crate::orphans::Report`].
+    #[serde(with = "out_stream")]
+    pub orphans: Option<StreamIdent>,
+    /// Globs for the scanned markup files that are entry points,
+    /// from which all other pages should be reachable.
+    #[serde(with = "crate::ignore_link::globs")]
+    pub entry_points: Vec<WildMatch>,
+    /// Where to write the backlinks index to, if anywhere.
+    /// See [`crate::backlinks::index`].
+    #[serde(with = "out_stream")]
+    pub backlinks: Option<StreamIdent>,
+    /// Where to write the summary statistics to, if anywhere.
+    /// See [`crate::summary::Summary`].
+    #[serde(with = "out_stream")]
+    pub summary: Option<StreamIdent>,
+    /// Where to write the document-level graph of the links to, if anywhere.
+    /// See [`crate::graph::Graph`].
+    #[serde(with = "out_stream")]
+    pub graph: Option<StreamIdent>,
+    /// The format of [`Self::graph`];
+    /// independent of [`Self::result_format`].
+    pub graph_format: result::GraphType,
     pub result_format: result::Type,
     /// Whether to include non-essential information in the resulting report.
     /// Non-essential are things like:
@@ -78,6 +305,11 @@ pub struct Tool {
     /// Whether to flush output streams after each item (link, anchor, error),
     /// for the result formats that support it.
     pub result_flush: bool,
+    /// Whether to write each distinct canonical link target only once,
+    /// together with all the locations it is linked from
+    /// and all the fragments linked to.
+    /// See [`crate::group::by_target`].
+    pub result_grouped: bool,
 }
 
 impl Default for Extractor {
@@ -87,6 +319,21 @@ impl Default for Extractor {
             links: true,
             anchors: false,
             ignore_links: Vec::default(),
+            ignore_anchors: Vec::default(),
+            ignore_files: Vec::default(),
+            link_filter: LinkFilter::default(),
+            code_languages: Vec::default(),
+            markup_type: None,
+            git_rev: None,
+            type_overrides: Vec::default(),
+            sniff_content: false,
+            default_encoding: None,
+            slug_scheme: SlugScheme::default(),
+            base_url: None,
+            site_root: None,
+            canonical_targets: false,
+            url_mappings: Vec::default(),
+            overrides: Vec::default(),
         }
     }
 }
@@ -97,9 +344,429 @@ impl Default for Tool {
             extractor: Extractor::default(),
             links: Some(StreamIdent::StdOut),
             anchors: None,
+            ignored: None,
+            validation: None,
+            remote_cache: None,
+            baseline: None,
+            write_baseline: None,
+            diff_old: None,
+            diff_new: None,
+            orphans: None,
+            entry_points: vec![
+                WildMatch::new("README.md"),
+                WildMatch::new("index.html"),
+                WildMatch::new("index.md"),
+            ],
+            backlinks: None,
+            summary: None,
+            graph: None,
+            graph_format: result::GraphType::default(),
             result_format: result::Type::default(),
             result_extended: false,
             result_flush: false,
+            result_grouped: false,
+        }
+    }
+}
+
+/// (De-)Serializes paths as plain strings.
+mod paths {
+    use cli_utils::path_buf::PathBuf;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(paths: &[PathBuf], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(paths.iter().map(|path| path.as_os_str().to_string_lossy()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<PathBuf>, D::Error> {
+        Vec::<PathBuf>::deserialize(deserializer)
+    }
+}
+
+/// Deserializes an optional character encoding label,
+/// rejecting unknown ones.
+mod encoding_label {
+    use serde::{Deserialize, Deserializer, de::Error};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|label| crate::encoding::parse_label(&label).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// (De-)Serializes an optional path as a plain string.
+mod path_opt {
+    use cli_utils::path_buf::PathBuf;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        path: &Option<PathBuf>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match path {
+            None => serializer.serialize_none(),
+            Some(some_path) => serializer.serialize_some(&some_path.as_os_str().to_string_lossy()),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<PathBuf>, D::Error> {
+        Option::<PathBuf>::deserialize(deserializer)
+    }
+}
+
+/// (De-)Serializes an output stream as a plain path string,
+/// with `-` denoting stdout.
+mod out_stream {
+    use cli_utils::{STREAM_PATH_STR, StreamIdent, path_buf::PathBuf};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        stream: &Option<StreamIdent>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match stream {
+            None => serializer.serialize_none(),
+            Some(StreamIdent::StdIn | StreamIdent::StdOut) => {
+                serializer.serialize_some(STREAM_PATH_STR)
+            }
+            Some(StreamIdent::Path(path, _)) => {
+                serializer.serialize_some(&path.as_os_str().to_string_lossy())
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<StreamIdent>, D::Error> {
+        Ok(Option::<PathBuf>::deserialize(deserializer)?
+            .map(|path| StreamIdent::from((Some(path), false))))
+    }
+}
+
+impl Tool {
+    /// Searches for a [`FILE_NAME`] file in `dir` and its ancestors,
+    /// returning the first one found,
+    /// as a path relative to `dir` (e.g. `../../mle.toml`).
+    #[must_use]
+    pub fn find_file(dir: &Path) -> Option<std::path::PathBuf> {
+        dir.ancestors()
+            .enumerate()
+            .find(|(_, ancestor)| ancestor.join(FILE_NAME).is_file())
+            .map(|(depth, _)| {
+                std::iter::repeat_n(Path::new(".."), depth)
+                    .collect::<std::path::PathBuf>()
+                    .join(FILE_NAME)
+            })
+    }
+
+    /// Loads the configuration from a TOML file.
+    /// Any settings not given in the file are left at their default.
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be read or is not a valid configuration.
+    pub fn from_file(path: &Path) -> Result<Self, FileError> {
+        let path_str = path.display().to_string();
+        let content =
+            std::fs::read_to_string(path).map_err(|err| FileError::Read(path_str.clone(), err))?;
+        let mut conf: Self =
+            toml::from_str(&content).map_err(|err| FileError::Parse(path_str, err))?;
+        conf.sync_streams();
+        if let Some(dir) = path.parent() {
+            conf.rebase_paths(dir);
+        }
+        Ok(conf)
+    }
+
+    /// Makes all the relative paths in this configuration
+    /// relative to `dir` instead,
+    /// so the paths in a configuration file
+    /// are relative to the directory it is in,
+    /// not to the working directory.
+    /// Stdin/stdout (`-`) and URLs are left as they are.
+    pub fn rebase_paths(&mut self, dir: &Path) {
+        if dir.as_os_str().is_empty() {
+            return;
+        }
+        let rebase = |path: &mut PathBuf| {
+            let path_str = path.as_os_str().to_string_lossy();
+            if path.is_relative()
+                && path_str != cli_utils::STREAM_PATH_STR
+                && !path_str.contains("://")
+            {
+                *path = PathBuf::from(dir.join(path.as_ref() as &Path));
+            }
+        };
+        let rebase_opt = |path: &mut Option<PathBuf>| {
+            if let Some(known) = path {
+                rebase(known);
+            }
+        };
+        self.extractor.markup_files.iter_mut().for_each(rebase);
+        rebase_opt(&mut self.extractor.site_root);
+        for path_override in &mut self.extractor.overrides {
+            rebase_opt(&mut path_override.site_root);
+        }
+        if let Some(rel_dir) = relative_to_cwd(dir) {
+            for mapping in &mut self.extractor.url_mappings {
+                mapping.path = rel_dir.join_normalized(&mapping.path);
+            }
+        } else if !self.extractor.url_mappings.is_empty() {
+            log::warn!(
+                "Can not express '{}' relative to the working directory, \
+so the paths of the URL mappings are left as they are",
+                dir.display()
+            );
+        }
+        for path in [
+            &mut self.remote_cache,
+            &mut self.baseline,
+            &mut self.write_baseline,
+            &mut self.diff_old,
+            &mut self.diff_new,
+        ] {
+            rebase_opt(path);
+        }
+        for stream in [
+            &mut self.links,
+            &mut self.anchors,
+            &mut self.ignored,
+            &mut self.validation,
+            &mut self.orphans,
+            &mut self.backlinks,
+            &mut self.summary,
+            &mut self.graph,
+        ] {
+            if let Some(StreamIdent::Path(path, _)) = stream {
+                rebase(path);
+            }
+        }
+    }
+
+    /// Serializes the configuration to TOML,
+    /// in the format read by [`Self::from_file`].
+    ///
+    /// # Errors
+    ///
+    /// If a setting can not be represented in TOML.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+
+    /// As TOML has no representation for `None`,
+    /// [`Extractor::links`] and [`Extractor::anchors`] decide
+    /// whether [`Self::links`] and [`Self::anchors`] are set,
+    /// falling back to stdout.
+    /// Setting a file as stream implies extracting what is written to it.
+    /// Stdout does not, as it is the default of [`Self::links`].
+    fn sync_streams(&mut self) {
+        self.extractor.links |= matches!(self.links, Some(StreamIdent::Path(..)));
+        self.extractor.anchors |= matches!(self.anchors, Some(StreamIdent::Path(..)));
+        self.links = self
+            .extractor
+            .links
+            .then(|| self.links.take().unwrap_or(StreamIdent::StdOut));
+        self.anchors = self
+            .extractor
+            .anchors
+            .then(|| self.anchors.take().unwrap_or(StreamIdent::StdOut));
+    }
+}
+
+/// Expresses `dir` relative to the working directory,
+/// which is what the paths of URL mappings are relative to.
+fn relative_to_cwd(dir: &Path) -> Option<RelativePathBuf> {
+    if dir.is_relative() {
+        return RelativePathBuf::from_path(dir).ok();
+    }
+    let cwd = std::env::current_dir().ok()?;
+    let common = cwd
+        .components()
+        .zip(dir.components())
+        .take_while(|(cwd_part, dir_part)| cwd_part == dir_part)
+        .count();
+    if common == 0 {
+        // e.g. different drives on Windows
+        return None;
+    }
+    let mut rel_dir = RelativePathBuf::new();
+    for _ in cwd.components().skip(common) {
+        rel_dir.push("..");
+    }
+    for part in dir.components().skip(common) {
+        rel_dir.push(part.as_os_str().to_str()?);
+    }
+    Some(rel_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_round_trip() {
+        let conf = Tool::default();
+        let serialized = conf.to_toml().expect("Serialization to succeed");
+        let mut parsed: Tool = toml::from_str(&serialized).expect("Parsing to succeed");
+        parsed.sync_streams();
+        assert_eq!(
+            serialized,
+            parsed.to_toml().expect("Serialization to succeed")
+        );
+    }
+
+    #[test]
+    fn path_overrides() {
+        let conf: Extractor = toml::from_str(
+            r#"
+ignore_links = ["*.png"]
+
+[[overrides]]
+glob = "vendor/*"
+slug_scheme = "Pandoc"
+
+[[overrides]]
+glob = "*/blog/*"
+ignore_links = ["https://twitter.com/*"]
+base_url = "https://example.com/blog/"
+site_root = "site"
+"#,
+        )
+        .expect("Parsing to succeed");
+        assert!(matches!(conf.for_file("README.md"), Cow::Borrowed(_)));
+        let vendor = conf.for_file("./vendor/lib/README.md");
+        assert_eq!(vendor.slug_scheme, SlugScheme::Pandoc);
+        assert_eq!(vendor.ignore_links.len(), 1);
+        let blog = conf.for_file("site/blog/post.md");
+        assert_eq!(blog.slug_scheme, SlugScheme::Gfm);
+        assert_eq!(blog.ignore_links.len(), 2);
+        assert_eq!(
+            blog.base_url.as_ref().map(Url::as_str),
+            Some("https://example.com/blog/")
+        );
+        assert_eq!(blog.site_root, Some(PathBuf::from("site")));
+        assert_eq!(vendor.site_root, None);
+    }
+
+    #[test]
+    fn toml_partial() {
+        let mut conf: Tool = toml::from_str(
+            r#"
+result_extended = true
+result_format = "tsv"
+graph_format = "graphml"
+links = "links.txt"
+
+[extractor]
+anchors = true
+ignore_links = ["https://example.com/*"]
+markup_type = "md"
+"#,
+        )
+        .expect("Parsing to succeed");
+        conf.sync_streams();
+        assert!(conf.result_extended);
+        assert!(matches!(conf.result_format, result::Type::Tsv));
+        assert_eq!(conf.graph_format, result::GraphType::GraphMl);
+        assert_eq!(conf.anchors, Some(StreamIdent::StdOut));
+        assert_eq!(
+            conf.links,
+            Some(StreamIdent::Path(PathBuf::from("links.txt"), false))
+        );
+        assert_eq!(conf.extractor.ignore_links.len(), 1);
+        assert_eq!(conf.extractor.markup_type, Some(markup::Type::Markdown));
+        assert!(conf.extractor.code_languages.is_empty());
+    }
+
+    #[test]
+    fn rebase_paths() {
+        let mut conf: Tool = toml::from_str(
+            r#"
+links = "links.txt"
+summary = "-"
+baseline = "/abs/baseline.json"
+
+[extractor]
+links = true
+markup_files = ["page.md", "-", "https://example.com/page.md"]
+site_root = "site"
+url_mappings = [{ path = "site", url = "https://example.com/" }]
+"#,
+        )
+        .expect("Parsing to succeed");
+        conf.sync_streams();
+        conf.rebase_paths(Path::new("../.."));
+        assert_eq!(
+            conf.extractor.markup_files,
+            vec![
+                PathBuf::from("../../page.md"),
+                PathBuf::from("-"),
+                PathBuf::from("https://example.com/page.md"),
+            ]
+        );
+        assert_eq!(conf.extractor.site_root, Some(PathBuf::from("../../site")));
+        assert_eq!(conf.extractor.url_mappings[0].path.as_str(), "../../site");
+        assert_eq!(
+            conf.links,
+            Some(StreamIdent::Path(PathBuf::from("../../links.txt"), false))
+        );
+        assert_eq!(conf.summary, Some(StreamIdent::StdOut));
+        assert_eq!(conf.baseline, Some(PathBuf::from("/abs/baseline.json")));
+
+        let cwd = std::env::current_dir().expect("A working directory");
+        let mut conf: Tool = toml::from_str(
+            r#"extractor.url_mappings = [{ path = "site", url = "https://example.com/" }]"#,
+        )
+        .expect("Parsing to succeed");
+        conf.rebase_paths(&cwd.join("docs"));
+        assert_eq!(conf.extractor.url_mappings[0].path.as_str(), "docs/site");
+        if let Some(parent) = cwd.parent() {
+            let mut conf: Tool = toml::from_str(
+                r#"extractor.url_mappings = [{ path = "site", url = "https://example.com/" }]"#,
+            )
+            .expect("Parsing to succeed");
+            conf.rebase_paths(parent);
+            assert_eq!(conf.extractor.url_mappings[0].path.as_str(), "../site");
         }
     }
+
+    #[test]
+    fn streams_imply_extraction() {
+        let mut conf: Tool = toml::from_str(
+            r#"
+anchors = "anchors.txt"
+
+[extractor]
+links = false
+"#,
+        )
+        .expect("Parsing to succeed");
+        conf.sync_streams();
+        assert!(conf.extractor.anchors);
+        assert_eq!(
+            conf.anchors,
+            Some(StreamIdent::Path(PathBuf::from("anchors.txt"), false))
+        );
+        assert!(!conf.extractor.links);
+        assert_eq!(conf.links, None);
+    }
+
+    #[test]
+    fn unknown_encoding() {
+        let err = toml::from_str::<Extractor>(r#"default_encoding = "latin-99""#)
+            .expect_err("Parsing to fail");
+        assert!(err.to_string().contains("Unknown character encoding"));
+        let conf: Extractor =
+            toml::from_str(r#"default_encoding = "latin1""#).expect("Parsing to succeed");
+        assert!(conf.default_encoding().is_some());
+    }
 }
@@ -0,0 +1,397 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Comparison of the links and anchors of two trees,
+//! or of two earlier JSON results of this tool.
+//!
+//! Items are compared by the file they are in and their value
+//! (the target of a link, or the name of an anchor).
+//! Items that only shifted within their file are considered unchanged;
+//! items that disappeared from one file and appeared in an other
+//! are reported as moved.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+use async_std::fs;
+use async_std::path::Path;
+use cli_utils::path_buf::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::anchor::Anchor;
+use crate::graph::document_id;
+use crate::link::{FileLoc, FileSystemLoc, Link, Locator, Target};
+use crate::{BoxResult, Config};
+
+/// Where an item was found.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Serialize)]
+pub struct Location {
+    /// The normalized path of the file,
+    /// relative to the root of its tree
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// A link or an anchor, as compared.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Item {
+    pub location: Location,
+    /// The target of a link, or the name of an anchor
+    pub value: String,
+}
+
+/// The links and anchors of one side of a diff.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Items {
+    pub links: Vec<Item>,
+    pub anchors: Vec<Item>,
+}
+
+/// What happened to an item between the old and the new side.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Change {
+    /// Only on the new side
+    Added,
+    /// Only on the old side
+    Removed,
+    /// In a different file on the new side
+    Moved,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added => write!(f, "added"),
+            Self::Removed => write!(f, "removed"),
+            Self::Moved => write!(f, "moved"),
+        }
+    }
+}
+
+/// One changed item.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Entry {
+    pub change: Change,
+    /// The target of a link, or the name of an anchor
+    pub value: String,
+    /// Where the item was on the old side; `None` if added
+    pub old: Option<Location>,
+    /// Where the item is on the new side; `None` if removed
+    pub new: Option<Location>,
+}
+
+impl Entry {
+    /// Where the item is now, or was, if it was removed.
+    #[must_use]
+    pub fn location(&self) -> Option<&Location> {
+        self.new.as_ref().or(self.old.as_ref())
+    }
+}
+
+/// The target of a link as compared,
+/// consistent between links and JSON link records.
+fn target_value(target: &str, fragment: Option<&str>) -> String {
+    fragment.map_or_else(|| target.to_owned(), |frag| format!("{target}#{frag}"))
+}
+
+/// The target of a link as compared.
+fn link_value(target: &Target) -> String {
+    target_value(&target.without_fragment().to_string(), target.fragment())
+}
+
+/// The id of `file` relative to `root`.
+fn relative_file(file: &str, root: &str) -> String {
+    file.strip_prefix(root)
+        .and_then(|rel| rel.strip_prefix('/'))
+        .unwrap_or(file)
+        .to_owned()
+}
+
+impl Items {
+    /// The items found when extracting from `root`.
+    /// File paths are made relative to `root`.
+    #[must_use]
+    pub fn from_extraction(links: &[Link], anchors: &[Anchor], root: &str) -> Self {
+        let location = |source: &Locator| Location {
+            file: relative_file(&document_id(&source.file), root),
+            line: source.pos.line,
+            column: source.pos.column,
+        };
+        Self {
+            links: links
+                .iter()
+                .map(|link| Item {
+                    location: location(&link.source),
+                    value: link_value(&link.target),
+                })
+                .collect(),
+            anchors: anchors
+                .iter()
+                .map(|anchor| Item {
+                    location: location(&anchor.source),
+                    value: anchor.name.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Parses a JSON result of this tool,
+    /// containing either links or anchors,
+    /// in the simple or the extended format.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON is malformed,
+    /// or its records are neither links nor anchors.
+    pub fn from_result_json(json: &str) -> serde_json::Result<Self> {
+        let records: Vec<Record> = serde_json::from_str(json)?;
+        let mut items = Self::default();
+        for record in records {
+            match record {
+                Record::Link {
+                    src_file,
+                    src_line,
+                    src_column,
+                    trg_link,
+                    trg_fragment,
+                } => items.links.push(Item {
+                    location: Location {
+                        file: src_file,
+                        line: src_line,
+                        column: src_column,
+                    },
+                    value: target_value(&trg_link, trg_fragment.as_deref()),
+                }),
+                Record::Anchor {
+                    src_file,
+                    src_line,
+                    src_column,
+                    name,
+                } => items.anchors.push(Item {
+                    location: Location {
+                        file: src_file,
+                        line: src_line,
+                        column: src_column,
+                    },
+                    value: name,
+                }),
+            }
+        }
+        Ok(items)
+    }
+
+    /// Loads the items of one side of a diff:
+    /// If `path` is a directory, the markup files of `conf`
+    /// are extracted relative to it, skipping those that do not exist there;
+    /// otherwise, it is read as a JSON result.
+    ///
+    /// # Errors
+    ///
+    /// If the JSON result could not be read or parsed,
+    /// or if `path` is a directory, but there are no markup files to scan.
+    pub async fn load(path: &PathBuf, conf: &Config) -> BoxResult<Self> {
+        let fs_path: &Path = path.as_ref();
+        if !fs_path.is_dir().await {
+            let json = fs::read_to_string(fs_path).await?;
+            return Ok(Self::from_result_json(&json)?);
+        }
+        if conf.markup_files.is_empty() {
+            return Err(
+                format!("No markup files provided to scan relative to the root '{path}'").into(),
+            );
+        }
+        let mut root_conf = conf.clone();
+        root_conf.markup_files.clear();
+        for file in &conf.markup_files {
+            let root_file = path.join(file);
+            let root_file_path: &Path = root_file.as_ref();
+            if root_file_path.exists().await {
+                root_conf.markup_files.push(root_file);
+            } else {
+                log::debug!("Not in '{path}': '{file}'");
+            }
+        }
+        let extraction = crate::extract(&root_conf).await;
+        crate::result::write_to_stderr(&extraction.errors);
+        let root = document_id(&FileLoc::System(FileSystemLoc::from(fs_path)));
+        Ok(Self::from_extraction(
+            &extraction.links,
+            &extraction.anchors,
+            &root,
+        ))
+    }
+}
+
+/// The fields of the JSON link and anchor records
+/// that are relevant for a diff.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Record {
+    Link {
+        src_file: String,
+        src_line: usize,
+        src_column: usize,
+        trg_link: String,
+        trg_fragment: Option<String>,
+    },
+    Anchor {
+        src_file: String,
+        src_line: usize,
+        src_column: usize,
+        name: String,
+    },
+}
+
+/// The locations of items, by file and value.
+fn by_file_and_value(items: &[Item]) -> BTreeMap<(&str, &str), Vec<&Location>> {
+    let mut grouped: BTreeMap<(&str, &str), Vec<&Location>> = BTreeMap::new();
+    for item in items {
+        grouped
+            .entry((item.location.file.as_str(), item.value.as_str()))
+            .or_default()
+            .push(&item.location);
+    }
+    grouped
+}
+
+/// Compares the `old` and `new` items,
+/// sorted by where the changed items are now, or were.
+#[must_use]
+pub fn compare(old: &[Item], new: &[Item]) -> Vec<Entry> {
+    let old_grouped = by_file_and_value(old);
+    let new_grouped = by_file_and_value(new);
+    let keys: BTreeSet<&(&str, &str)> = old_grouped.keys().chain(new_grouped.keys()).collect();
+    let mut removed: BTreeMap<&str, VecDeque<&Location>> = BTreeMap::new();
+    let mut added: Vec<(&str, &Location)> = vec![];
+    for key in keys {
+        let mut old_locs = old_grouped.get(key).cloned().unwrap_or_default();
+        let mut new_locs = new_grouped.get(key).cloned().unwrap_or_default();
+        new_locs.retain(|new_loc| {
+            old_locs
+                .iter()
+                .position(|old_loc| old_loc == new_loc)
+                .map(|idx| old_locs.remove(idx))
+                .is_none()
+        });
+        // The remaining ones of both sides were shifted within the file
+        let shifted = old_locs.len().min(new_locs.len());
+        removed
+            .entry(key.1)
+            .or_default()
+            .extend(old_locs.into_iter().skip(shifted));
+        added.extend(new_locs.into_iter().skip(shifted).map(|loc| (key.1, loc)));
+    }
+    let mut entries = vec![];
+    for (value, new_loc) in added {
+        let moved_from = removed.get_mut(value).and_then(VecDeque::pop_front);
+        entries.push(Entry {
+            change: if moved_from.is_some() {
+                Change::Moved
+            } else {
+                Change::Added
+            },
+            value: value.to_owned(),
+            old: moved_from.cloned(),
+            new: Some(new_loc.clone()),
+        });
+    }
+    for (value, old_locs) in removed {
+        for old_loc in old_locs {
+            entries.push(Entry {
+                change: Change::Removed,
+                value: value.to_owned(),
+                old: Some(old_loc.clone()),
+                new: None,
+            });
+        }
+    }
+    entries.sort_by(|entry_a, entry_b| {
+        (entry_a.location(), &entry_a.value, entry_a.change).cmp(&(
+            entry_b.location(),
+            &entry_b.value,
+            entry_b.change,
+        ))
+    });
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(file: &str, line: usize, value: &str) -> Item {
+        Item {
+            location: Location {
+                file: file.to_owned(),
+                line,
+                column: 1,
+            },
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn change_names() {
+        for value in [Change::Added, Change::Removed, Change::Moved] {
+            assert_eq!(
+                serde_json::to_string(&value).expect("Serializable"),
+                format!("\"{value}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn changes() {
+        let old = [
+            item("README.md", 1, "docs/a.md"),
+            item("README.md", 2, "docs/gone.md"),
+            item("docs/a.md", 3, "https://example.org"),
+        ];
+        let new = [
+            item("README.md", 5, "docs/a.md"),
+            item("README.md", 6, "docs/new.md"),
+            item("docs/b.md", 1, "https://example.org"),
+        ];
+        let entries = compare(&old, &new);
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.change, entry.value.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Change::Removed, "docs/gone.md"),
+                (Change::Added, "docs/new.md"),
+                (Change::Moved, "https://example.org"),
+            ]
+        );
+        assert_eq!(
+            entries[2].old.as_ref().map(ToString::to_string),
+            Some("docs/a.md:3:1".to_owned())
+        );
+    }
+
+    #[test]
+    fn result_json() {
+        let items = Items::from_result_json(
+            r#"[
+  {"src_file": "a.md", "src_line": 1, "src_column": 2, "trg_link": "b.md", "trg_fragment": "x"},
+  {"src_file": "a.md", "src_line": 3, "src_column": 1, "name": "intro", "type": "TitleAuto"}
+]"#,
+        )
+        .expect("Valid JSON");
+        assert_eq!(items.links.len(), 1);
+        assert_eq!(items.links[0].location.to_string(), "a.md:1:2");
+        assert_eq!(items.links[0].value, "b.md#x");
+        assert_eq!(items.anchors, vec![item("a.md", 3, "intro")]);
+    }
+}
@@ -0,0 +1,201 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Decoding of markup content in character encodings other than UTF-8.
+
+use std::sync::LazyLock;
+
+use encoding_rs::{Encoding, UTF_8};
+use regex::bytes::Regex;
+use thiserror::Error;
+
+/// How many bytes from the start of the content are searched
+/// for an encoding declaration.
+const DECLARATION_SCAN_LEN: usize = 1024;
+
+/// `<meta charset="...">`, `<meta http-equiv="Content-Type" content="...; charset=...">`
+/// and `<?xml ... encoding="..."?>`
+static DECLARATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i-u)<meta[^>]*?charset[ \t\r\n]*=[ \t\r\n]*["']?(?P<meta>[A-Za-z0-9_.:-]+)|<\?xml[^>]*?encoding[ \t\r\n]*=[ \t\r\n]*["'](?P<xml>[A-Za-z0-9_.:-]+)"#,
+    )
+    .unwrap()
+});
+
+/// The content could not be decoded with the chosen character encoding.
+#[derive(Debug, Error)]
+#[error("Content is not valid {encoding}{}", reason_suffix(*.reason))]
+pub struct DecodingError {
+    /// The name of the character encoding that was tried
+    pub encoding: &'static str,
+    /// Why that encoding was chosen
+    pub reason: Reason,
+}
+
+/// Why a character encoding was chosen for decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    /// A byte order mark at the start of the content
+    Bom,
+    /// A `<meta charset>` or XML declaration
+    Declaration,
+    /// The configured default encoding
+    Default,
+    /// Neither of the above was given, so UTF-8 was assumed
+    Assumed,
+}
+
+const fn reason_suffix(reason: Reason) -> &'static str {
+    match reason {
+        Reason::Bom => " (as indicated by its byte order mark)",
+        Reason::Declaration => " (as declared in it)",
+        Reason::Default => " (the configured default encoding)",
+        Reason::Assumed => "; consider configuring a default encoding",
+    }
+}
+
+/// Finds the character encoding declared within the content,
+/// e.g. with `<meta charset="ISO-8859-1">` in HTML,
+/// or `<?xml version="1.0" encoding="ISO-8859-1"?>` in XML.
+#[must_use]
+pub fn find_declared(content: &[u8]) -> Option<&'static Encoding> {
+    let head = &content[..content.len().min(DECLARATION_SCAN_LEN)];
+    let caps = DECLARATION.captures(head)?;
+    let label = caps.name("meta").or_else(|| caps.name("xml"))?;
+    Encoding::for_label(label.as_bytes())
+}
+
+/// Parses the argument as the label of a character encoding,
+/// e.g. `"latin1"` or `"Shift_JIS"`.
+///
+/// # Errors
+///
+/// If the label does not denote a known encoding.
+pub fn parse_label(label: &str) -> Result<String, String> {
+    Encoding::for_label(label.as_bytes())
+        .map(|_| label.to_owned())
+        .ok_or_else(|| format!("Unknown character encoding: '{label}'"))
+}
+
+/// Decodes raw content into a string.
+///
+/// The encoding is chosen by (in this order of precedence):
+///
+/// 1. a byte order mark
+/// 2. UTF-8, if the content is valid UTF-8
+/// 3. an encoding declaration within the content (see [`find_declared`])
+/// 4. the given default encoding
+///
+/// Valid UTF-8 takes precedence over declarations,
+/// because those might as well be part of the text,
+/// e.g. an HTML snippet in a code block of a Markdown file.
+///
+/// # Errors
+///
+/// If the content is not valid in the chosen encoding.
+pub fn decode(
+    content: Vec<u8>,
+    default: Option<&'static Encoding>,
+) -> Result<String, DecodingError> {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(&content) {
+        return decode_with(&content[bom_len..], encoding, Reason::Bom);
+    }
+    let non_utf8 = match String::from_utf8(content) {
+        Ok(text) => return Ok(text),
+        Err(err) => err.into_bytes(),
+    };
+    if let Some(encoding) = find_declared(&non_utf8) {
+        // NOTE A UTF-16 document can not really declare itself as such,
+        //      as we would not have been able to read the declaration;
+        //      see <https://html.spec.whatwg.org/#prescan-a-byte-stream-to-determine-its-encoding>
+        return decode_with(&non_utf8, encoding.output_encoding(), Reason::Declaration);
+    }
+    decode_with(
+        &non_utf8,
+        default.unwrap_or(UTF_8),
+        if default.is_some() {
+            Reason::Default
+        } else {
+            Reason::Assumed
+        },
+    )
+}
+
+fn decode_with(
+    content: &[u8],
+    encoding: &'static Encoding,
+    reason: Reason,
+) -> Result<String, DecodingError> {
+    encoding
+        .decode_without_bom_handling_and_without_replacement(content)
+        .map(std::borrow::Cow::into_owned)
+        .ok_or_else(|| DecodingError {
+            encoding: encoding.name(),
+            reason,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf8() {
+        assert_eq!(
+            decode("Äpfel".as_bytes().to_vec(), None).ok(),
+            Some("Äpfel".to_owned())
+        );
+    }
+
+    #[test]
+    fn bom() {
+        let mut utf16le = vec![0xFF, 0xFE];
+        for unit in "<a href=\"x\">".encode_utf16() {
+            utf16le.extend(unit.to_le_bytes());
+        }
+        assert_eq!(
+            decode(utf16le, None).ok(),
+            Some("<a href=\"x\">".to_owned())
+        );
+        let utf8_bom = b"\xEF\xBB\xBFabc".to_vec();
+        assert_eq!(decode(utf8_bom, None).ok(), Some("abc".to_owned()));
+    }
+
+    #[test]
+    fn declared() {
+        let html =
+            b"<html><head><meta charset=\"ISO-8859-1\"></head><body>\xC4pfel</body>".to_vec();
+        assert!(decode(html, None).is_ok_and(|text| text.contains("Äpfel")));
+        let meta =
+            b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=windows-1252\">\x80"
+                .to_vec();
+        assert!(decode(meta, None).is_ok_and(|text| text.ends_with('€')));
+        let xml = b"<?xml version='1.0' encoding='iso-8859-15'?><a>\xA4</a>".to_vec();
+        assert!(decode(xml, None).is_ok_and(|text| text.contains('€')));
+    }
+
+    #[test]
+    fn utf8_before_declaration() {
+        let markdown = "# Über\n\n```html\n<meta charset=\"ISO-8859-1\">\n```\n"
+            .as_bytes()
+            .to_vec();
+        assert!(decode(markdown, None).is_ok_and(|text| text.starts_with("# Über")));
+    }
+
+    #[test]
+    fn default() {
+        let latin1 = b"\xC4pfel".to_vec();
+        assert!(decode(latin1.clone(), None).is_err_and(|err| err.reason == Reason::Assumed));
+        assert_eq!(
+            decode(latin1, Encoding::for_label(b"latin1")).ok(),
+            Some("Äpfel".to_owned())
+        );
+    }
+
+    #[test]
+    fn undecodable() {
+        let bad = b"<meta charset=\"utf-8\">\xFF".to_vec();
+        assert!(decode(bad, None).is_err_and(|err| err.reason == Reason::Declaration));
+    }
+}
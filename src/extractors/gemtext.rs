@@ -0,0 +1,196 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use crate::anchor::{self, Anchor};
+use crate::config::Extractor as Config;
+use crate::link::{Link, Locator, Position};
+use crate::markup::File;
+
+/// Extracts links and heading anchors from Gemini gemtext files.
+///
+/// See the [gemtext specification](https://geminiprotocol.net/docs/gemtext-specification.gmi).
+pub struct LinkExtractor();
+
+const LINK_PREFIX: &str = "=>";
+const PREFORMAT_TOGGLE: &str = "```";
+
+/// Generates the anchor name of a heading,
+/// the way common gemtext to HTML converters do:
+/// The text is lower-cased,
+/// every run of non-alphanumeric characters is replaced by a single `-`,
+/// and leading and trailing `-` are removed.
+fn generate_id(text: &str) -> String {
+    let mut id = String::with_capacity(text.len());
+    let mut pending_dash = false;
+    for chr in text.chars() {
+        if chr.is_alphanumeric() {
+            if pending_dash && !id.is_empty() {
+                id.push('-');
+            }
+            pending_dash = false;
+            id.extend(chr.to_lowercase());
+        } else {
+            pending_dash = true;
+        }
+    }
+    id
+}
+
+impl super::LinkExtractor for LinkExtractor {
+    async fn find_links_and_anchors<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
+        &self,
+        file: &File<'_>,
+        conf: &Config,
+        links_receiver: &mut LR,
+        anchors_receiver: &mut AR,
+    ) -> std::io::Result<()> {
+        let content = file.fetch().await?;
+        let mut preformatted = false;
+        let mut used_ids = HashMap::new();
+        for (line_idx, line) in content.lines().enumerate() {
+            if line.starts_with(PREFORMAT_TOGGLE) {
+                preformatted = !preformatted;
+                continue;
+            }
+            if preformatted {
+                continue;
+            }
+            if let Some(link_line) = line.strip_prefix(LINK_PREFIX) {
+                if !conf.links {
+                    continue;
+                }
+                let url_line = link_line.trim_start_matches([' ', '\t']);
+                let Some(url) = url_line
+                    .split([' ', '\t'])
+                    .next()
+                    .filter(|url| !url.is_empty())
+                else {
+                    continue;
+                };
+                let url_idx = line.len() - url_line.len();
+                let pos = Position {
+                    line: line_idx + 1,
+                    column: line[..url_idx].chars().count() + 1,
+                } + &file.start;
+                links_receiver(Link::new(file.locator.clone(), pos, url)).await;
+            } else if line.starts_with('#') && conf.anchors {
+                let text = line.trim_start_matches('#').trim();
+                let id = generate_id(text);
+                if id.is_empty() {
+                    continue;
+                }
+                let pos = Position {
+                    line: line_idx + 1,
+                    column: 1,
+                } + &file.start;
+                anchors_receiver(Anchor {
+                    source: Locator {
+                        file: file.locator.clone(),
+                        pos,
+                    },
+                    name: anchor::deduplicate(id, &mut used_ids),
+                    r#type: anchor::Type::TitleAuto,
+                })
+                .await;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::link::{FileLoc, Target};
+    use crate::markup::Type;
+
+    use super::*;
+    use ntest::test_case;
+
+    macro_rules! aw_through_engine {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    async fn find_links(content: &str) -> Vec<Link> {
+        let markup_file = File::dummy(content, Type::Gemtext);
+        let conf = Config::default();
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .map(|parsed| parsed.links)
+            .expect("No error")
+    }
+
+    async fn find_anchors(content: &str) -> Vec<Anchor> {
+        let markup_file = File::dummy(content, Type::Gemtext);
+        let conf = Config {
+            links: false,
+            anchors: true,
+            ..Config::default()
+        };
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .map(|parsed| parsed.anchors)
+            .expect("No error")
+    }
+
+    #[test_case("=> gemini://example.net/", 1, 4)]
+    #[test_case("=>gemini://example.net/", 1, 3)]
+    #[test_case("=> \t gemini://example.net/ The label", 1, 6)]
+    #[test_case("# Title\n\n=> gemini://example.net/ Ä label", 3, 4)]
+    fn gemtext_links(input: &str, line: usize, column: usize) {
+        let result = aw_through_engine!(find_links(input));
+        let expected = Link::new(
+            FileLoc::dummy(),
+            Position { line, column },
+            "gemini://example.net/",
+        );
+        assert_eq!(vec![expected], result);
+    }
+
+    #[test_case("```\n=> gemini://example.net/\n```")]
+    #[test_case("Text => gemini://example.net/")]
+    #[test_case("=>")]
+    #[test_case(" => gemini://example.net/")]
+    fn gemtext_no_links(input: &str) {
+        let result = aw_through_engine!(find_links(input));
+        assert!(result.is_empty());
+    }
+
+    #[tokio::test]
+    async fn gemini_target() {
+        let result = find_links("=> gemini://example.net/page.gmi#sub").await;
+        assert!(matches!(result[0].target, Target::Gemini(_)));
+        assert_eq!(result[0].target.fragment(), Some("sub"));
+    }
+
+    #[test_case("# My Capsule", "my-capsule")]
+    #[test_case("##  Über uns, & more!", "über-uns-more")]
+    #[test_case("###Version 1.2", "version-1-2")]
+    fn gemtext_anchors(input: &str, name: &str) {
+        let result = aw_through_engine!(find_anchors(input));
+        let expected = Anchor {
+            source: Locator {
+                file: FileLoc::dummy(),
+                pos: Position { line: 1, column: 1 },
+            },
+            name: name.to_owned(),
+            r#type: anchor::Type::TitleAuto,
+        };
+        assert_eq!(vec![expected], result);
+    }
+
+    #[tokio::test]
+    async fn gemtext_duplicate_anchors() {
+        let input = "# Intro\n## Intro\n```\n# Not a heading\n```\n### Intro";
+        let names: Vec<String> = find_anchors(input)
+            .await
+            .into_iter()
+            .map(|anchor| anchor.name)
+            .collect();
+        assert_eq!(vec!["intro", "intro-1", "intro-2"], names);
+    }
+}
@@ -7,6 +7,7 @@ use crate::anchor::{self, Anchor};
 use crate::config::Extractor as Config;
 use crate::link::{self, Link, Position};
 use crate::markup::File;
+use crate::suppress::{Directive, Suppressions};
 
 pub struct LinkExtractor();
 
@@ -31,6 +32,10 @@ enum Attribute {
 struct Scanner<'a> {
     line: &'a str,
     chars: Vec<char>,
+    /// The byte index of each char within `line`,
+    /// followed by the length of `line`
+    byte_idxs: Vec<usize>,
+    /// The current position, in chars
     column: usize,
 }
 
@@ -39,6 +44,7 @@ impl<'a> Scanner<'a> {
         Scanner {
             line: "",
             chars: vec![],
+            byte_idxs: vec![],
             column: 0,
         }
     }
@@ -46,16 +52,25 @@ impl<'a> Scanner<'a> {
     pub fn reset(&mut self, new_line: &'a str) {
         self.line = new_line;
         self.chars = new_line.chars().collect();
+        self.byte_idxs = new_line
+            .char_indices()
+            .map(|(idx, _chr)| idx)
+            .chain([new_line.len()])
+            .collect();
         self.column = 0;
     }
 
+    /// The part of the line between two char positions.
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        &self.line[self.byte_idxs[start]..self.byte_idxs[end]]
+    }
+
     pub fn take(&mut self, token: &str) -> bool {
-        let found = self
-            .line
-            .get(self.column..self.column + token.len())
-            .is_some_and(|slc| slc.eq(token));
+        let token_len = token.chars().count();
+        let found = self.column + token_len < self.byte_idxs.len()
+            && self.slice(self.column, self.column + token_len) == token;
         if found {
-            self.column += token.len();
+            self.column += token_len;
         }
         found
     }
@@ -110,7 +125,7 @@ impl<'a> Scanner<'a> {
             count += 1;
         }
         self.column += count;
-        &self.line[self.column - count..self.column]
+        self.slice(self.column - count, self.column)
     }
 
     pub fn take_non_ws_or_in(&mut self, token: &str) -> &'a str {
@@ -122,7 +137,7 @@ impl<'a> Scanner<'a> {
             count += 1;
         }
         self.column += count;
-        &self.line[self.column - count..self.column]
+        self.slice(self.column - count, self.column)
     }
 
     pub fn take_non(&mut self, token: char) -> Option<&'a str> {
@@ -137,7 +152,7 @@ impl<'a> Scanner<'a> {
         }
         if found || count > 0 {
             self.column += count;
-            Some(&self.line[self.column - count..self.column])
+            Some(self.slice(self.column - count, self.column))
         } else {
             None
         }
@@ -162,20 +177,42 @@ impl super::LinkExtractor for LinkExtractor {
         let mut is_anchor = false;
         // let mut element_part: Option<Attribute>;
         let mut scanner = Scanner::empty();
-        for (line, line_str) in file.content.fetch().await?.as_ref().lines().enumerate() {
+        // NOTE We collect everything first,
+        //      because an `mle-ignore-file` directive may come last.
+        let mut links = vec![];
+        let mut anchors = vec![];
+        let mut suppressions = Suppressions::default();
+        let mut comment_start = Position::new();
+        let mut comment = String::new();
+        for (line, line_str) in file.fetch().await?.as_ref().lines().enumerate() {
             scanner.reset(line_str);
+            if matches!(state, ParserState::Comment) {
+                comment.push('\n');
+            }
             while !scanner.is_done() {
                 match state {
                     ParserState::Comment => {
                         if scanner.take("-->") {
                             state = ParserState::Text;
-                        } else {
-                            scanner.take_any();
+                            if let Some(directive) = Directive::parse(&comment) {
+                                let comment_end = Position {
+                                    line: line + 1,
+                                    column: scanner.column + 1,
+                                } + &file.start;
+                                suppressions.add(directive, &comment_start, &comment_end);
+                            }
+                        } else if let Some(chr) = scanner.take_any() {
+                            comment.push(chr);
                         }
                     }
                     ParserState::Text => {
                         if scanner.take("<!--") {
                             state = ParserState::Comment;
+                            comment_start = Position {
+                                line: line + 1,
+                                column: scanner.column - 3,
+                            } + &file.start;
+                            comment.clear();
                         } else if scanner.take("<!") {
                             // e.g. <!DOCTYPE
                             state = ParserState::DocType;
@@ -266,38 +303,35 @@ impl super::LinkExtractor for LinkExtractor {
                             match attrib_cont {
                                 Attribute::Href => {
                                     if conf.links {
-                                        links_receiver(Link::new(
+                                        links.push(Link::new(
                                             file.locator.clone(),
                                             pos,
                                             attrib_target,
-                                        ))
-                                        .await;
+                                        ));
                                     }
                                 }
                                 Attribute::Name => {
                                     if conf.anchors {
-                                        anchors_receiver(Anchor {
+                                        anchors.push(Anchor {
                                             source: link::Locator {
                                                 file: file.locator.clone(),
                                                 pos,
                                             },
                                             name: attrib_target.to_string(),
                                             r#type: anchor::Type::Direct,
-                                        })
-                                        .await;
+                                        });
                                     }
                                 }
                                 Attribute::Id => {
                                     if conf.anchors {
-                                        anchors_receiver(Anchor {
+                                        anchors.push(Anchor {
                                             source: link::Locator {
                                                 file: file.locator.clone(),
                                                 pos,
                                             },
                                             name: attrib_target.to_string(),
                                             r#type: anchor::Type::ElementId,
-                                        })
-                                        .await;
+                                        });
                                     }
                                 }
                                 Attribute::Other => {}
@@ -309,6 +343,9 @@ impl super::LinkExtractor for LinkExtractor {
                 }
             }
         }
+        suppressions
+            .emit(links, anchors, links_receiver, anchors_receiver)
+            .await;
         Ok(())
     }
 }
@@ -567,4 +604,28 @@ mod tests {
         let result = aw_through_engine!(find_anchors(input)).expect("No error");
         assert_eq!(Vec::<Anchor>::new(), result);
     }
+
+    #[tokio::test]
+    async fn suppression_directives() {
+        let input = r#"<a href="a.html">a</a>
+<!--
+  mle-ignore-next-line
+-->
+<a href="b.html">b</a> <a id="b">b</a>
+<!-- mle-disable --><a href="c.html">c</a><!-- mle-enable --><a href="d.html">d</a>
+<!-- not a directive --><a href="e.html">e</a>"#;
+        let targets: Vec<String> = find_links(input)
+            .await
+            .expect("No error")
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(targets, vec!["a.html", "d.html", "e.html"]);
+        let anchors = find_anchors(input).await.expect("No error");
+        assert!(anchors.is_empty());
+        let ignored = find_links("<a href=\"a.html\">a</a><!--mle-ignore-file-->")
+            .await
+            .expect("No error");
+        assert!(ignored.is_empty());
+    }
 }
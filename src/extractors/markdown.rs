@@ -3,6 +3,7 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::collections::HashMap;
 use std::sync::LazyLock;
 
 use crate::anchor;
@@ -14,29 +15,14 @@ use crate::link::Position;
 use crate::markup;
 use crate::markup::Content;
 use crate::markup::File;
-use pulldown_cmark::{BrokenLink, Event, Options, Parser, Tag};
+use crate::suppress::Suppressions;
+use pulldown_cmark::{BrokenLink, Event, Options, Parser, Tag, TagEnd};
 use regex::Regex;
 
 pub struct LinkExtractor();
 
-static NON_ID_CHARS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^A-Za-z0-9 -]").unwrap());
-static LEADING_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[0-9]+").unwrap());
 static CHECK_BOX_VALUES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[ xX]?$").unwrap());
 
-/// 1. downcase the headline
-/// 2. remove anything that is not a letter, number, space or hyphen
-/// 3. change any space to a hyphen
-/// 4. if pandoc-style, remove leading numbers
-fn generate_id(text: &str, gfm_style: bool) -> String {
-    let mut id = NON_ID_CHARS
-        .replace_all(&text.to_lowercase(), "")
-        .replace(' ', "-");
-    if !gfm_style {
-        id = LEADING_NUMBER.replace(&id, "").to_string();
-    }
-    id
-}
-
 impl LinkExtractor {
     fn create_pos_from_idx(content: &str) -> impl Fn(usize) -> Position + use<> {
         let line_lengths: Vec<usize> = content.lines().map(str::len).collect();
@@ -56,17 +42,19 @@ impl LinkExtractor {
 }
 
 impl super::LinkExtractor for LinkExtractor {
+    #[allow(clippy::too_many_lines)]
     async fn find_links_and_anchors<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
         &self,
         file: &File<'_>,
         conf: &Config,
-        mut links_receiver: &mut LR,
-        mut anchors_receiver: &mut AR,
+        links_receiver: &mut LR,
+        anchors_receiver: &mut AR,
     ) -> std::io::Result<()> {
         let html_le = super::html::LinkExtractor();
 
-        // let line_lengths: Vec<usize> = file.content.fetch()?.lines().map(str::len).collect();
-        let pos_from_idx = Self::create_pos_from_idx(file.content.fetch().await?.as_ref());
+        // NOTE We may only fetch once, as e.g. stdin can not be read twice
+        let text = file.fetch().await?;
+        let pos_from_idx = Self::create_pos_from_idx(text.as_ref());
 
         let callback = &mut |broken_link: BrokenLink| {
             let refrnc = broken_link.reference.as_ref();
@@ -104,7 +92,6 @@ impl super::LinkExtractor for LinkExtractor {
             None
         };
 
-        let text = file.content.fetch().await?;
         let parser = Parser::new_with_broken_link_callback(
             &text,
             Options::ENABLE_HEADING_ATTRIBUTES,
@@ -113,96 +100,125 @@ impl super::LinkExtractor for LinkExtractor {
 
         let mut gathering_for_header = false;
         let mut header_content: Vec<String> = Vec::new();
+        let mut header_pos = Position::new();
+        let mut used_ids = HashMap::new();
+        // NOTE We collect everything first,
+        //      because an `mle-ignore-file` directive may come last.
+        let mut links = vec![];
+        let mut anchors = vec![];
+        let mut suppressions = Suppressions::default();
+        // NOTE An HTML block arrives as one `Event::Html` per line,
+        //      so we collect it whole to find directives in multi-line comments.
+        let mut html_block: Option<(Position, String)> = None;
         for (evt, range) in parser.into_offset_iter() {
             match evt {
-                Event::Start(Tag::Heading {level: _, id, classes: _, attrs: _})
-                if conf.anchors && id.is_none() => {
-                    // if let Tag::Heading(_level, id, _classes) = tag {
-                    gathering_for_header = true;
-                    // }
-                    // match tag {
-                    //     Tag::Heading(_level, id, _classes) => {
-                    //         if gathering_anchors && id.is_none() {
-                    //             gathering_for_header = true;
-                    //         }
-                    //     },
-                    //     _ => (),
-                    // }
-                }
                 Event::Start(tag) => {
                     match tag {
-                        Tag::Link {link_type: _, dest_url, title: _, id: _}
-                        | Tag::Image {link_type: _, dest_url, title: _, id: _}
-                        if conf.links => {
+                        Tag::Link {
+                            link_type: _,
+                            dest_url,
+                            title: _,
+                            id: _,
+                        }
+                        | Tag::Image {
+                            link_type: _,
+                            dest_url,
+                            title: _,
+                            id: _,
+                        } if conf.links => {
                             let pos = pos_from_idx(range.start) + &file.start;
-                            links_receiver(Link::new(
-                                file.locator.clone(),
-                                pos,
-                                &dest_url,
-                            )).await;
+                            links.push(Link::new(file.locator.clone(), pos, &dest_url));
                         }
-                        Tag::Heading {level: _, id, classes: _, attrs: _}
-                        if conf.anchors => {
+                        Tag::Heading {
+                            level: _,
+                            id,
+                            classes: _,
+                            attrs: _,
+                        } if conf.anchors => {
                             let pos = pos_from_idx(range.start) + &file.start;
-                            let source = Locator {
-                                file: file.locator.clone(),
-                                pos,
-                            };
-                            let r#type : anchor::Type;
-                            let id_str : String = if let Some(id_cont) = id {
-                                    r#type = anchor::Type::TitleManual;
-                                    // eprint!("XXX Title with manual id!: '{}'\n", id_cont);
-                                    id_cont.to_string()
-                                } else {
-                                    r#type = anchor::Type::TitleAuto;
-                                    gathering_for_header = false;
-                                    // let header_text = header_content.iter().filter(|txt| txt.is_empty()).collect::<Vec<&String>>().join(" ");
-                                    // let header_text = header_content.iter()
-                                    //         //.filter(|txt| !txt.is_empty())
-                                    //         .fold(String::new(), |acc, s| {
-                                    //             eprint!("YYY part: '{}'\n", s);
-                                    //             acc + "" + s
-                                    //         });
-                                    //         // .collect::<Vec<_>>()
-                                    //         // .join(" ");
-                                    let header_text = header_content.join("");
-                                    /*let id = */generate_id(&header_text, true)//; // TODO need config setting for GFM vs Pandoc rules
-                                    // eprint!(fmt"XXX Title with auto id!: '{header_text}'\n");
-                                    // eprint!("XXX Title with auto id!: '{}'\n", id);
-                                    // id
-                            };
-                            header_content.clear();
-                            anchors_receiver(Anchor {
-                                source,
-                                name: id_str,
-                                r#type,
-                            }).await;
+                            if let Some(id_cont) = id {
+                                anchors.push(Anchor {
+                                    source: Locator {
+                                        file: file.locator.clone(),
+                                        pos,
+                                    },
+                                    name: id_cont.to_string(),
+                                    r#type: anchor::Type::TitleManual,
+                                });
+                            } else {
+                                // NOTE The anchor name is only known
+                                //      once we have seen the whole title,
+                                //      see `Event::End(TagEnd::Heading(_))`.
+                                header_pos = pos;
+                                gathering_for_header = true;
+                            }
+                        }
+                        Tag::HtmlBlock => {
+                            let pos = pos_from_idx(range.start) + &file.start;
+                            html_block = Some((pos, String::new()));
                         }
                         _ => (),
                     }
                 }
-                Event::Html(content) | Event::InlineHtml(content) /* TODO FALL_THROUGH_TO_NEXT_THREE, OR ... (see TODO below) */ => {
-                    let cur_pos = pos_from_idx(range.start) + &file.start - Position { line: 1, column: 0 };
-                    let sub_markup = File {
-                        markup_type: markup::Type::Html,
-                        locator: file.locator.clone(),
-                        content: Content::InMemory(content.as_ref()),
-                        start: cur_pos,
-                    };
-                    html_le.find_links_and_anchors(&sub_markup, conf, &mut links_receiver, &mut anchors_receiver).await?;
-
-                    if gathering_for_header { // TODO ... OR_THIS (see TODO above)
-                        header_content.push(content.into_string());
+                Event::End(TagEnd::HtmlBlock) => {
+                    if let Some((pos, block)) = html_block.take() {
+                        suppressions.add_from_html(&block, &pos);
                     }
                 }
-                Event::Text(content)
-                | Event::Code(content)
-                | Event::FootnoteReference(content) if gathering_for_header => {
+                Event::End(TagEnd::Heading(_)) if gathering_for_header => {
+                    gathering_for_header = false;
+                    let name = conf.slug_scheme.slugify(&header_content.join(""));
+                    header_content.clear();
+                    anchors.push(Anchor {
+                        source: Locator {
+                            file: file.locator.clone(),
+                            pos: std::mem::take(&mut header_pos),
+                        },
+                        name: anchor::deduplicate(name, &mut used_ids),
+                        r#type: anchor::Type::TitleAuto,
+                    });
+                }
+                Event::Html(content) | Event::InlineHtml(content) => {
+                    if let Some((_, block)) = html_block.as_mut() {
+                        block.push_str(&content);
+                    } else {
+                        suppressions
+                            .add_from_html(&content, &(pos_from_idx(range.start) + &file.start));
+                    }
+                    let cur_pos =
+                        pos_from_idx(range.start) + &file.start - Position { line: 1, column: 0 };
+                    // NOTE JSX in MDX is handled by the MDX extractor itself,
+                    //      as it would confuse the HTML extractor.
+                    if file.markup_type != markup::Type::Mdx {
+                        let sub_markup = File {
+                            markup_type: markup::Type::Html,
+                            locator: file.locator.clone(),
+                            content: Content::InMemory(content.as_ref()),
+                            start: cur_pos,
+                            default_encoding: file.default_encoding,
+                        };
+                        html_le
+                            .find_links_and_anchors(
+                                &sub_markup,
+                                conf,
+                                &mut async |link: Link| links.push(link),
+                                &mut async |anchor: Anchor| anchors.push(anchor),
+                            )
+                            .await?;
+                    }
+                    // NOTE HTML tags do not contribute to a titles auto-generated anchor.
+                }
+                Event::Text(content) | Event::Code(content) | Event::FootnoteReference(content)
+                    if gathering_for_header =>
+                {
                     header_content.push(content.into_string());
                 }
                 _ => (),
             }
         }
+        suppressions
+            .emit(links, anchors, links_receiver, anchors_receiver)
+            .await;
         Ok(())
     }
 }
@@ -656,4 +672,117 @@ c) [X](c)
         let result = find_links(input).await;
         assert_eq!(result.len(), 18);
     }
+
+    async fn find_anchors(
+        content: &str,
+        slug_scheme: anchor::SlugScheme,
+    ) -> Vec<(String, anchor::Type)> {
+        let markup_file = File::dummy(content, markup::Type::Markdown);
+        let conf = Config {
+            anchors: true,
+            slug_scheme,
+            ..Config::default()
+        };
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .expect("No error")
+            .anchors
+            .into_iter()
+            .map(|anchor| (anchor.name, anchor.r#type))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn title_anchors() {
+        let input = "# 1. Intro `code`\n\n## Setup {#custom}\n\n## Intro\n\n# 1. Intro `code`\n\n## Über_uns\n";
+        assert_eq!(
+            find_anchors(input, anchor::SlugScheme::Gfm).await,
+            vec![
+                ("1-intro-code".to_owned(), anchor::Type::TitleAuto),
+                ("custom".to_owned(), anchor::Type::TitleManual),
+                ("intro".to_owned(), anchor::Type::TitleAuto),
+                ("1-intro-code-1".to_owned(), anchor::Type::TitleAuto),
+                ("über_uns".to_owned(), anchor::Type::TitleAuto),
+            ]
+        );
+        assert_eq!(
+            find_anchors("# 1. Intro `code`", anchor::SlugScheme::Pandoc).await,
+            vec![("intro-code".to_owned(), anchor::Type::TitleAuto)]
+        );
+    }
+
+    #[tokio::test]
+    async fn base_url() {
+        let markup_file = File::dummy(
+            "[a](guide.md#x) [b](/root.html) [c](#local) [d](https://example.net/)",
+            markup::Type::Markdown,
+        );
+        let conf = Config {
+            base_url: Some(Url::parse("https://example.com/docs/").expect("Valid URL")),
+            ..Config::default()
+        };
+        let targets: Vec<String> = super::super::gather_links(&markup_file, &conf)
+            .await
+            .expect("No error")
+            .links
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                "https://example.com/docs/guide.md#x",
+                "https://example.com/root.html",
+                "#local",
+                "https://example.net/",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn suppression_directives() {
+        let input = "[a](a.md)
+<!-- mle-ignore-next-line -->
+[b](b.md)
+Text <!-- mle-disable --> [c](c.md)
+
+<a href=\"d.md\">d</a> [e](e.md) <!-- mle-enable --> [f](f.md)
+";
+        let targets: Vec<String> = find_links(input)
+            .await
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(targets, vec!["a.md", "f.md"]);
+        let ignored = find_links("[a](a.md)\n\n<!-- mle-ignore-file -->\n").await;
+        assert!(ignored.is_empty());
+    }
+
+    #[tokio::test]
+    async fn multi_line_suppression_directives() {
+        let input = "[a](a.md)
+
+<!--
+  mle-ignore-next-line
+-->
+[b](b.md)
+
+<!--
+mle-disable
+-->
+
+[c](c.md)
+
+<!-- mle-enable
+-->
+
+[d](d.md)
+";
+        let targets: Vec<String> = find_links(input)
+            .await
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(targets, vec!["a.md", "d.md"]);
+    }
 }
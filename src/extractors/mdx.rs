@@ -0,0 +1,320 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::sync::LazyLock;
+
+use pulldown_cmark::{Event, Parser, Tag};
+use regex::Regex;
+
+use crate::anchor::{self, Anchor};
+use crate::config::Extractor as Config;
+use crate::link::{Link, Locator};
+use crate::markup::{self, Content, File};
+
+pub struct LinkExtractor();
+
+/// An ESM statement, which in MDX has to start at the beginning of a line.
+static ESM_START: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(import|export)[ \t{*]").unwrap());
+static CODE_FENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^ {0,3}(```|~~~)").unwrap());
+/// HTML comments (`<!-- ... -->`) and MDX comments (`{/* ... */}`)
+static COMMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->|\{/\*.*?\*/\}").unwrap());
+/// An opening JSX/HTML tag, including all its props;
+/// may span multiple lines.
+static JSX_TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<[A-Za-z][A-Za-z0-9_.:-]*(?:[^>"'{}]|"[^"]*"|'[^']*'|\{[^}]*\})*/?>"#).unwrap()
+});
+/// A JSX prop with a string literal value,
+/// either given directly (`to="..."`, `to='...'`)
+/// or as a string expression (``to={"..."}``, ``to={'...'}``, ``to={`...`}``).
+static JSX_PROP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?:^|[ \t\r\n])(?P<name>to|href|src|id)[ \t\r\n]*=[ \t\r\n]*(?:"(?P<dq>[^"]*)"|'(?P<sq>[^']*)'|\{[ \t\r\n]*(?:"(?P<edq>[^"]*)"|'(?P<esq>[^']*)'|`(?P<ebt>[^`$]*)`)[ \t\r\n]*\})"#,
+    )
+    .unwrap()
+});
+
+/// Replaces all ESM (`import`/`export`) blocks with empty lines,
+/// so the remaining content can be parsed as plain Markdown,
+/// while all positions stay valid.
+///
+/// An ESM block starts with a line beginning with `import` or `export`,
+/// and ends with the next blank line, just like a Markdown paragraph.
+fn blank_esm(content: &str) -> String {
+    let mut in_esm = false;
+    let mut in_code = false;
+    let mut blanked = String::with_capacity(content.len());
+    for line in content.lines() {
+        if !in_esm && CODE_FENCE.is_match(line) {
+            in_code = !in_code;
+        }
+        if !in_code && !in_esm && ESM_START.is_match(line) {
+            in_esm = true;
+        }
+        if in_esm && line.trim().is_empty() {
+            in_esm = false;
+        }
+        if !in_esm {
+            blanked.push_str(line);
+        }
+        blanked.push('\n');
+    }
+    blanked
+}
+
+/// Replaces every character of every comment with a space,
+/// except for line breaks, so all positions stay valid.
+fn blank_comments(content: &str) -> String {
+    COMMENT
+        .replace_all(content, |caps: &regex::Captures| {
+            caps[0]
+                .chars()
+                .map(|chr| if chr == '\n' { '\n' } else { ' ' })
+                .collect::<String>()
+        })
+        .into_owned()
+}
+
+/// Replaces all code spans and code blocks with white-space,
+/// so JSX-like text inside of them is not mistaken for JSX,
+/// while all positions stay valid.
+fn blank_code(content: &str) -> String {
+    let mut code_ranges = vec![];
+    for (evt, range) in Parser::new(content).into_offset_iter() {
+        if matches!(evt, Event::Code(_) | Event::Start(Tag::CodeBlock(_))) {
+            code_ranges.push(range);
+        }
+    }
+    let mut blanked = String::with_capacity(content.len());
+    let mut last_end = 0;
+    for range in code_ranges {
+        if range.start < last_end {
+            continue;
+        }
+        blanked.push_str(&content[last_end..range.start]);
+        blanked.extend(
+            content[range.clone()]
+                .chars()
+                .map(|chr| if chr == '\n' { '\n' } else { ' ' }),
+        );
+        last_end = range.end;
+    }
+    blanked.push_str(&content[last_end..]);
+    blanked
+}
+
+/// Finds links and anchors in the props of JSX elements.
+///
+/// Only props with a string literal value are considered,
+/// because the value of any other expression is only known at runtime.
+async fn find_jsx_links_and_anchors<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
+    file: &File<'_>,
+    content: &str,
+    conf: &Config,
+    links_receiver: &mut LR,
+    anchors_receiver: &mut AR,
+) {
+    let jsx_content = blank_comments(&blank_code(content));
+    for tag in JSX_TAG.find_iter(&jsx_content) {
+        for prop in JSX_PROP.captures_iter(tag.as_str()) {
+            let Some(value) = ["dq", "sq", "edq", "esq", "ebt"]
+                .iter()
+                .find_map(|group| prop.name(group))
+            else {
+                continue;
+            };
+            let pos =
+                super::pos_from_byte_idx(&jsx_content, tag.start() + value.start()) + &file.start;
+            match &prop["name"] {
+                "id" => {
+                    if conf.anchors {
+                        anchors_receiver(Anchor {
+                            source: Locator {
+                                file: file.locator.clone(),
+                                pos,
+                            },
+                            name: value.as_str().to_owned(),
+                            r#type: anchor::Type::ElementId,
+                        })
+                        .await;
+                    }
+                }
+                _ => {
+                    if conf.links {
+                        links_receiver(Link::new(file.locator.clone(), pos, value.as_str())).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl super::LinkExtractor for LinkExtractor {
+    async fn find_links_and_anchors<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
+        &self,
+        file: &File<'_>,
+        conf: &Config,
+        links_receiver: &mut LR,
+        anchors_receiver: &mut AR,
+    ) -> std::io::Result<()> {
+        let content = blank_esm(file.fetch().await?.as_ref());
+        // We gather everything first,
+        // so we can report the JSX and the Markdown parts in document order.
+        let mut links = vec![];
+        let mut anchors = vec![];
+        let links_gatherer = &mut async |link: Link| links.push(link);
+        let anchors_gatherer = &mut async |anchor: Anchor| anchors.push(anchor);
+        find_jsx_links_and_anchors(file, &content, conf, links_gatherer, anchors_gatherer).await;
+        let md_file = File {
+            markup_type: markup::Type::Mdx,
+            locator: file.locator.clone(),
+            content: Content::InMemory(&content),
+            start: file.start.clone(),
+            default_encoding: file.default_encoding,
+        };
+        super::markdown::LinkExtractor()
+            .find_links_and_anchors(&md_file, conf, links_gatherer, anchors_gatherer)
+            .await?;
+        links.sort_by_key(|link| (link.source.pos.line, link.source.pos.column));
+        anchors.sort_by_key(|anchor| (anchor.source.pos.line, anchor.source.pos.column));
+        for link in links {
+            links_receiver(link).await;
+        }
+        for anchor in anchors {
+            anchors_receiver(anchor).await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::link::{FileLoc, Position};
+
+    use super::*;
+    use ntest::test_case;
+
+    macro_rules! aw_through_engine {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    async fn find_links(content: &str) -> Vec<Link> {
+        let markup_file = File::dummy(content, markup::Type::Mdx);
+        let conf = Config::default();
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .map(|parsed| parsed.links)
+            .expect("No error")
+    }
+
+    async fn find_anchors(content: &str) -> Vec<Anchor> {
+        let markup_file = File::dummy(content, markup::Type::Mdx);
+        let conf = Config {
+            links: false,
+            anchors: true,
+            ..Config::default()
+        };
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .map(|parsed| parsed.anchors)
+            .expect("No error")
+    }
+
+    #[tokio::test]
+    async fn esm_is_skipped() {
+        let input = "import Link from '@docusaurus/Link';
+export const meta = {
+  see: '[not](https://example.net/esm)',
+};
+
+[link](https://example.net/)
+";
+        let result = find_links(input).await;
+        let expected = Link::new(
+            FileLoc::dummy(),
+            Position { line: 6, column: 1 },
+            "https://example.net/",
+        );
+        assert_eq!(vec![expected], result);
+    }
+
+    #[tokio::test]
+    async fn esm_in_code_block_is_kept() {
+        let input = "```
+import x from 'y';
+```
+
+[link](https://example.net/)
+";
+        let result = find_links(input).await;
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test_case(r#"<Link to="/docs/x">X</Link>"#, 1, 11)]
+    #[test_case("<Link to='/docs/x'>X</Link>", 1, 11)]
+    #[test_case(r#"<Link to={"/docs/x"}>X</Link>"#, 1, 12)]
+    #[test_case("<Link to={'/docs/x'}>X</Link>", 1, 12)]
+    #[test_case("<Link to={`/docs/x`}>X</Link>", 1, 12)]
+    #[test_case("Some text <Link\n  className=\"x\"\n  to={ '/docs/x' }\n/>", 3, 9)]
+    #[test_case(r#"<img src="/docs/x" alt={props.alt} />"#, 1, 11)]
+    #[test_case(r#"<a href="/docs/x" onClick={() => go()}>X</a>"#, 1, 10)]
+    fn jsx_links(input: &str, line: usize, column: usize) {
+        let result = aw_through_engine!(find_links(input));
+        let expected = Link::new(FileLoc::dummy(), Position { line, column }, "/docs/x");
+        assert_eq!(vec![expected], result);
+    }
+
+    #[test_case("<Link to={target}>X</Link>")]
+    #[test_case("<Link to={`/docs/${name}`}>X</Link>")]
+    #[test_case(r#"{/* <Link to="/docs/x">X</Link> */}"#)]
+    #[test_case(r#"<!-- <Link to="/docs/x">X</Link> -->"#)]
+    fn jsx_no_links(input: &str) {
+        let result = aw_through_engine!(find_links(input));
+        assert!(result.is_empty());
+    }
+
+    #[tokio::test]
+    async fn mixed_in_document_order() {
+        let input = "[first](https://example.net/1)
+
+<Link to={'https://example.net/2'}>second</Link>
+
+[third](https://example.net/3) and `<Link to='/code'>`
+";
+        let result: Vec<String> = find_links(input)
+            .await
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "https://example.net/1",
+                "https://example.net/2",
+                "https://example.net/3"
+            ],
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn jsx_anchor() {
+        let input = r#"<div id="the_anchor">X</div>"#;
+        let result = find_anchors(input).await;
+        let expected = Anchor {
+            source: Locator {
+                file: FileLoc::dummy(),
+                pos: Position {
+                    line: 1,
+                    column: 10,
+                },
+            },
+            name: "the_anchor".to_owned(),
+            r#type: anchor::Type::ElementId,
+        };
+        assert_eq!(vec![expected], result);
+    }
+}
@@ -3,17 +3,28 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+mod gemtext;
 mod html;
 mod markdown;
+mod mdx;
+mod plain;
+mod xml;
+
+use std::borrow::Cow;
+
+use cli_utils::path_buf::PathBuf;
 
 use crate::anchor::Anchor;
 use crate::config::Extractor as Config;
-use crate::link::Link;
+use crate::ignored::{self, Kind as IgnoredKind};
+use crate::link::{FileLoc, FileSystemTarget, Link, Position, Target};
 use crate::markup::{self, File};
 
 pub struct ParseRes {
     pub links: Vec<Link>,
     pub anchors: Vec<Anchor>,
+    /// The links and anchors that were ignored, by rule
+    pub ignored: ignored::Report,
 }
 
 impl ParseRes {
@@ -35,6 +46,17 @@ pub fn remove_anchor(link: &mut String) -> Option<String> {
     })
 }
 
+/// Converts a byte index into `content` to a position,
+/// with the column counted in characters.
+pub(crate) fn pos_from_byte_idx(content: &str, idx: usize) -> Position {
+    let before = &content[..idx];
+    let line_start = before.rfind('\n').map_or(0, |nl_idx| nl_idx + 1);
+    Position {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
 /// Finds links (and optionally anchors),
 /// using the markup file specific link extractor internally.
 ///
@@ -67,25 +89,94 @@ pub async fn scan_for_links<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
 pub async fn gather_links(file: &File<'_>, conf: &Config) -> std::io::Result<ParseRes> {
     let mut links = vec![];
     let mut anchors = vec![];
-    let links_receiver = &mut async |link: Link| {
+    let mut ignored = ignored::Report::default();
+    let mut ignored_anchors = ignored::Report::default();
+    let cwd = if conf.canonical_targets || conf.site_root.is_some() {
+        match std::env::current_dir() {
+            Ok(cwd) => Some(PathBuf::from(cwd)),
+            Err(err) => {
+                log::warn!("Failed to get the working directory to resolve targets: {err}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let abs_site_root = conf.site_root.as_ref().map(|root| {
+        cwd.as_ref()
+            .map_or_else(|| root.clone(), |base| base.join(root))
+    });
+    let canonical_base = cwd.filter(|_| conf.canonical_targets);
+    let links_receiver = &mut async |found: Link| {
+        // NOTE Ignore globs and filters are matched against the target
+        //      as written in the file, as well as after the rewrites below.
+        let written = found.target.clone();
+        let mut link = match &conf.base_url {
+            Some(base_url) => Link {
+                target: found.target.with_base_url(base_url),
+                ..found
+            },
+            None => found,
+        };
+        if let Some(site_root) = &abs_site_root
+            && matches!(
+                link.source.file.as_ref(),
+                FileLoc::System(_) | FileLoc::GitRev { .. }
+            )
+        {
+            link.target = link.target.with_site_root(site_root);
+        }
+        if let Some(base) = &canonical_base {
+            match link.target.canonical(false, link.source.file.clone(), base) {
+                Ok(Cow::Owned(Target::FileSystem(fs_target))) => {
+                    link.target = Target::FileSystem(FileSystemTarget {
+                        file: fs_target.file.normalized(),
+                        ..fs_target
+                    });
+                }
+                Ok(Cow::Owned(canonical)) => link.target = canonical,
+                Ok(Cow::Borrowed(_)) => (),
+                Err(err) => log::warn!("Failed to canonicalize link target '{link}': {err}"),
+            }
+        }
+        let written_as_str = written.to_string();
+        let link_as_str = link.target.to_string();
         for link_ignorer in &conf.ignore_links {
-            let link_as_str = link.target.to_string();
-            if link_ignorer.matches(&link_as_str) {
+            if link_ignorer.matches(&written_as_str) || link_ignorer.matches(&link_as_str) {
+                ignored.record(IgnoredKind::Link, link_ignorer, link.to_string());
                 return;
             }
         }
+        if !conf.link_filter.accepts_any(&[&written, &link.target]) {
+            return;
+        }
         links.push(link);
     };
     let anchors_receiver = &mut async |anchor: Anchor| {
+        for anchor_ignorer in &conf.ignore_anchors {
+            if anchor_ignorer.matches(&anchor.name) {
+                ignored_anchors.record(IgnoredKind::Anchor, anchor_ignorer, anchor.to_string());
+                return;
+            }
+        }
         anchors.push(anchor);
     };
     scan_for_links(file, conf, links_receiver, anchors_receiver).await?;
-    Ok(ParseRes { links, anchors })
+    ignored.merge(ignored_anchors);
+    Ok(ParseRes {
+        links,
+        anchors,
+        ignored,
+    })
 }
 
 enum LinkExtractorCont {
     Markdown(markdown::LinkExtractor),
     Html(html::LinkExtractor),
+    Mdx(mdx::LinkExtractor),
+    Xml(xml::LinkExtractor),
+    Plain(plain::LinkExtractor),
+    Gemtext(gemtext::LinkExtractor),
 }
 
 impl LinkExtractor for LinkExtractorCont {
@@ -107,6 +198,26 @@ impl LinkExtractor for LinkExtractorCont {
                     .find_links_and_anchors(file, conf, links_receiver, anchors_receiver)
                     .await
             }
+            Self::Mdx(internal) => {
+                internal
+                    .find_links_and_anchors(file, conf, links_receiver, anchors_receiver)
+                    .await
+            }
+            Self::Xml(internal) => {
+                internal
+                    .find_links_and_anchors(file, conf, links_receiver, anchors_receiver)
+                    .await
+            }
+            Self::Plain(internal) => {
+                internal
+                    .find_links_and_anchors(file, conf, links_receiver, anchors_receiver)
+                    .await
+            }
+            Self::Gemtext(internal) => {
+                internal
+                    .find_links_and_anchors(file, conf, links_receiver, anchors_receiver)
+                    .await
+            }
         }
     }
 }
@@ -115,6 +226,15 @@ const fn link_extractor_factory(markup_type: markup::Type) -> LinkExtractorCont
     match markup_type {
         markup::Type::Markdown => LinkExtractorCont::Markdown(markdown::LinkExtractor()),
         markup::Type::Html => LinkExtractorCont::Html(html::LinkExtractor()),
+        markup::Type::Mdx => LinkExtractorCont::Mdx(mdx::LinkExtractor()),
+        markup::Type::Xml(profile) => LinkExtractorCont::Xml(xml::LinkExtractor(profile)),
+        markup::Type::Plain => LinkExtractorCont::Plain(plain::LinkExtractor {
+            comments_only: false,
+        }),
+        markup::Type::Code => LinkExtractorCont::Plain(plain::LinkExtractor {
+            comments_only: true,
+        }),
+        markup::Type::Gemtext => LinkExtractorCont::Gemtext(gemtext::LinkExtractor()),
     }
 }
 
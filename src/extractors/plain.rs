@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::anchor::Anchor;
+use crate::config::Extractor as Config;
+use crate::link::Link;
+use crate::markup::File;
+use crate::source_code::Language;
+
+/// Extracts URLs from plain text,
+/// or - in source-code mode - from the comments in source code.
+pub struct LinkExtractor {
+    pub comments_only: bool,
+}
+
+static URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:(?:https?|s?ftp|file|gemini)://|mailto:)[^ \t\r\n<>"'`\x00-\x1f\x7f]+"#)
+        .unwrap()
+});
+
+/// Removes trailing characters that are (most likely) not part of the URL,
+/// like sentence punctuation and closing brackets that were not opened
+/// within the URL.
+fn trim_url(url: &str) -> &str {
+    let mut trimmed = url;
+    loop {
+        let Some(last) = trimmed.chars().next_back() else {
+            return trimmed;
+        };
+        let unbalanced = |open: char| trimmed.matches(open).count() < trimmed.matches(last).count();
+        let strip = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' | '*' | '\\' => true,
+            ')' => unbalanced('('),
+            ']' => unbalanced('['),
+            '}' => unbalanced('{'),
+            _ => false,
+        };
+        if !strip {
+            return trimmed;
+        }
+        trimmed = &trimmed[..trimmed.len() - last.len_utf8()];
+    }
+}
+
+/// Finds all URLs in `text`, which starts at byte index `offset` of `content`,
+/// and reports them with their position in `content`.
+async fn find_urls<LR: AsyncFnMut(Link)>(
+    file: &File<'_>,
+    content: &str,
+    offset: usize,
+    text: &str,
+    links_receiver: &mut LR,
+) {
+    for url_match in URL.find_iter(text) {
+        let url = trim_url(url_match.as_str());
+        if url.ends_with("://") || url.ends_with(':') {
+            continue;
+        }
+        let pos = super::pos_from_byte_idx(content, offset + url_match.start()) + &file.start;
+        links_receiver(Link::new(file.locator.clone(), pos, url)).await;
+    }
+}
+
+impl super::LinkExtractor for LinkExtractor {
+    async fn find_links_and_anchors<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
+        &self,
+        file: &File<'_>,
+        conf: &Config,
+        links_receiver: &mut LR,
+        _anchors_receiver: &mut AR,
+    ) -> std::io::Result<()> {
+        if !conf.links {
+            // Plain text has no anchors
+            return Ok(());
+        }
+        let content = file.fetch().await?;
+        if self.comments_only {
+            let file_name = file.locator.to_string();
+            if let Some(language) = Language::find_by_file_name(&conf.code_languages, &file_name) {
+                for range in language.comment_ranges(&content) {
+                    let comment = &content[range.clone()];
+                    find_urls(file, &content, range.start, comment, links_receiver).await;
+                }
+                return Ok(());
+            }
+            log::warn!(
+                "No configured source-code language matches '{file_name}'; \
+scanning all of it for URLs, as plain text"
+            );
+        }
+        find_urls(file, &content, 0, &content, links_receiver).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::link::{FileLoc, Position};
+    use crate::markup::Type;
+
+    use super::*;
+    use ntest::test_case;
+
+    macro_rules! aw_through_engine {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    async fn find_links(content: &str, markup_type: Type, file_name: &str) -> Vec<Link> {
+        let markup_file = File {
+            locator: Arc::new(FileLoc::System(file_name.parse().expect("Infallible"))),
+            ..File::dummy(content, markup_type)
+        };
+        let conf = Config {
+            code_languages: Language::builtins(),
+            ..Config::default()
+        };
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .map(|parsed| parsed.links)
+            .expect("No error")
+    }
+
+    #[test_case("See https://example.net/x.", "https://example.net/x")]
+    #[test_case("(see https://example.net/x)", "https://example.net/x")]
+    #[test_case(
+        "https://en.wikipedia.org/wiki/Rust_(disambiguation)",
+        "https://en.wikipedia.org/wiki/Rust_(disambiguation)"
+    )]
+    #[test_case("<https://example.net/x>", "https://example.net/x")]
+    #[test_case("mail mailto:me@example.net, now", "mailto:me@example.net")]
+    #[test_case("'ftp://example.net/file.tar.gz'", "ftp://example.net/file.tar.gz")]
+    fn plain_urls(input: &str, url: &str) {
+        let result = aw_through_engine!(find_links(input, Type::Plain, "notes.txt"));
+        let targets: Vec<String> = result.iter().map(|link| link.target.to_string()).collect();
+        assert_eq!(vec![url.to_owned()], targets);
+    }
+
+    #[tokio::test]
+    async fn plain_positions() {
+        let input = "Licensed under\n  the Äpache, see <https://example.net/LICENSE>";
+        let result = find_links(input, Type::Plain, "LICENSE.txt").await;
+        let expected = Link::new(
+            Arc::new(FileLoc::System("LICENSE.txt".parse().expect("Infallible"))),
+            Position {
+                line: 2,
+                column: 20,
+            },
+            "https://example.net/LICENSE",
+        );
+        assert_eq!(vec![expected], result);
+    }
+
+    #[tokio::test]
+    async fn code_comments_only() {
+        let input = r#"//! Docs at https://example.net/docs
+const URL: &str = "https://example.net/not-a-comment";
+/* see
+   https://example.net/block */
+"#;
+        let result = find_links(input, Type::Code, "src/lib.rs").await;
+        let found: Vec<(usize, usize, String)> = result
+            .iter()
+            .map(|link| {
+                (
+                    link.source.pos.line,
+                    link.source.pos.column,
+                    link.target.to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (1, 13, "https://example.net/docs".to_owned()),
+                (4, 4, "https://example.net/block".to_owned()),
+            ],
+            found
+        );
+    }
+
+    #[tokio::test]
+    async fn code_unknown_language() {
+        let input = "x = 'https://example.net/'";
+        let result = find_links(input, Type::Code, "script.unknown").await;
+        assert_eq!(result.len(), 1);
+    }
+}
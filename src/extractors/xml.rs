@@ -0,0 +1,332 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::anchor::{self, Anchor};
+use crate::config::Extractor as Config;
+use crate::link::{Link, Locator};
+use crate::markup::{File, XmlProfile};
+
+pub struct LinkExtractor(pub XmlProfile);
+
+/// Everything that may look like markup, but is not:
+/// comments, CDATA sections, processing instructions
+/// (including the XML declaration) and the document type declaration
+static NON_ELEMENTS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<!--.*?-->|<!\[CDATA\[.*?\]\]>|<\?.*?\?>|<!DOCTYPE(?:[^\[>]|\[[^\]]*\])*>")
+        .unwrap()
+});
+/// An opening (or empty) element tag, including all its attributes;
+/// may span multiple lines.
+static START_TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<(?P<name>[A-Za-z_][A-Za-z0-9_.:-]*)(?:[^>"']|"[^"]*"|'[^']*')*>"#).unwrap()
+});
+static ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"[ \t\r\n](?P<name>[A-Za-z_][A-Za-z0-9_.:-]*)[ \t\r\n]*=[ \t\r\n]*(?:"(?P<dq>[^"]*)"|'(?P<sq>[^']*)')"#,
+    )
+    .unwrap()
+});
+
+/// What the value of an attribute represents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AttributeKind {
+    /// A URI reference
+    Link,
+    /// The ID of an element in the same document (e.g. `linkend` in `DocBook`)
+    IdRef,
+    /// The ID of this element
+    Anchor,
+}
+
+const SVG_LINKING_ELEMENTS: [&str; 9] = [
+    "a",
+    "image",
+    "use",
+    "feImage",
+    "pattern",
+    "linearGradient",
+    "radialGradient",
+    "textPath",
+    "mpath",
+];
+const DOCBOOK_IDREF_ELEMENTS: [&str; 2] = ["xref", "link"];
+const DITA_LINKING_ELEMENTS: [&str; 8] = [
+    "xref", "link", "topicref", "mapref", "keydef", "chapter", "image", "navref",
+];
+
+/// Splits a qualified XML name into its prefix (if any) and its local part.
+fn split_qname(qname: &str) -> (Option<&str>, &str) {
+    qname
+        .split_once(':')
+        .map_or((None, qname), |(prefix, local)| (Some(prefix), local))
+}
+
+/// Replaces the predefined XML entities and character references.
+fn unescape(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp_idx) = rest.find('&') {
+        unescaped.push_str(&rest[..amp_idx]);
+        rest = &rest[amp_idx..];
+        let replacement = rest.find(';').and_then(|semi_idx| {
+            let ref_chr = match &rest[1..semi_idx] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                num if num.starts_with("#x") => u32::from_str_radix(&num[2..], 16)
+                    .ok()
+                    .and_then(char::from_u32),
+                num if num.starts_with('#') => num[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            ref_chr.map(|chr| (chr, semi_idx + 1))
+        });
+        if let Some((chr, ref_len)) = replacement {
+            unescaped.push(chr);
+            rest = &rest[ref_len..];
+        } else {
+            unescaped.push('&');
+            rest = &rest[1..];
+        }
+    }
+    unescaped.push_str(rest);
+    Cow::Owned(unescaped)
+}
+
+impl LinkExtractor {
+    /// Figures out what an attribute represents,
+    /// according to the XML profile in use.
+    fn attribute_kind(&self, element: &str, attribute: &str) -> Option<AttributeKind> {
+        let (_elem_prefix, elem) = split_qname(element);
+        let (attr_prefix, attr) = split_qname(attribute);
+        if attr == "id" && matches!(attr_prefix, None | Some("xml")) {
+            return Some(AttributeKind::Anchor);
+        }
+        let svg = || SVG_LINKING_ELEMENTS.contains(&elem) && attr == "href";
+        let docbook = || {
+            if DOCBOOK_IDREF_ELEMENTS.contains(&elem) && attribute == "linkend" {
+                Some(AttributeKind::IdRef)
+            } else if (elem == "ulink" && attribute == "url") || attribute == "xlink:href" {
+                Some(AttributeKind::Link)
+            } else {
+                None
+            }
+        };
+        let dita = || {
+            (DITA_LINKING_ELEMENTS.contains(&elem) && attribute == "href") || attribute == "conref"
+        };
+        match self.0 {
+            XmlProfile::Svg => svg().then_some(AttributeKind::Link),
+            XmlProfile::DocBook => docbook(),
+            XmlProfile::Dita => dita().then_some(AttributeKind::Link),
+            XmlProfile::Generic => {
+                if svg() || dita() {
+                    Some(AttributeKind::Link)
+                } else {
+                    docbook()
+                }
+            }
+        }
+    }
+}
+
+impl super::LinkExtractor for LinkExtractor {
+    async fn find_links_and_anchors<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
+        &self,
+        file: &File<'_>,
+        conf: &Config,
+        links_receiver: &mut LR,
+        anchors_receiver: &mut AR,
+    ) -> std::io::Result<()> {
+        let fetched = file.fetch().await?;
+        // Blank out everything that is not an element,
+        // keeping line breaks, so all positions stay valid.
+        let content = NON_ELEMENTS.replace_all(fetched.as_ref(), |caps: &regex::Captures| {
+            caps[0]
+                .chars()
+                .map(|chr| if chr == '\n' { '\n' } else { ' ' })
+                .collect::<String>()
+        });
+        for tag in START_TAG.captures_iter(&content) {
+            let tag_match = tag.get(0).expect("Regex match always has group 0");
+            let element = &tag["name"];
+            for attrib in ATTRIBUTE.captures_iter(tag_match.as_str()) {
+                let Some(kind) = self.attribute_kind(element, &attrib["name"]) else {
+                    continue;
+                };
+                let Some(value) = attrib.name("dq").or_else(|| attrib.name("sq")) else {
+                    continue;
+                };
+                let pos = super::pos_from_byte_idx(&content, tag_match.start() + value.start())
+                    + &file.start;
+                let value_str = unescape(value.as_str());
+                match kind {
+                    AttributeKind::Link if conf.links => {
+                        links_receiver(Link::new(file.locator.clone(), pos, &value_str)).await;
+                    }
+                    AttributeKind::IdRef if conf.links => {
+                        links_receiver(Link::new(
+                            file.locator.clone(),
+                            pos,
+                            &format!("#{value_str}"),
+                        ))
+                        .await;
+                    }
+                    AttributeKind::Anchor if conf.anchors => {
+                        anchors_receiver(Anchor {
+                            source: Locator {
+                                file: file.locator.clone(),
+                                pos,
+                            },
+                            name: value_str.into_owned(),
+                            r#type: anchor::Type::ElementId,
+                        })
+                        .await;
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::link::{FileLoc, Position};
+    use crate::markup::Type;
+
+    use super::*;
+    use ntest::test_case;
+
+    macro_rules! aw_through_engine {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    async fn find_links(content: &str, profile: XmlProfile) -> Vec<Link> {
+        let markup_file = File::dummy(content, Type::Xml(profile));
+        let conf = Config::default();
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .map(|parsed| parsed.links)
+            .expect("No error")
+    }
+
+    async fn find_anchors(content: &str, profile: XmlProfile) -> Vec<Anchor> {
+        let markup_file = File::dummy(content, Type::Xml(profile));
+        let conf = Config {
+            links: false,
+            anchors: true,
+            ..Config::default()
+        };
+        super::super::gather_links(&markup_file, &conf)
+            .await
+            .map(|parsed| parsed.anchors)
+            .expect("No error")
+    }
+
+    #[test]
+    fn unescape_entities() {
+        assert_eq!(unescape("a?b=1&amp;c=2"), "a?b=1&c=2");
+        assert_eq!(unescape("&#x41;&#66;&lt;&unknown;"), "AB<&unknown;");
+    }
+
+    #[test_case(r#"<a xlink:href="https://example.net/">X</a>"#, 1, 16)]
+    #[test_case(r#"<a href="https://example.net/">X</a>"#, 1, 10)]
+    #[test_case("<svg:image\n  width='10'\n  href='https://example.net/'/>", 3, 9)]
+    #[test_case(
+        r#"<?xml version="1.0"?><use xlink:href="https://example.net/"/>"#,
+        1,
+        39
+    )]
+    fn svg_links(input: &str, line: usize, column: usize) {
+        let result = aw_through_engine!(find_links(input, XmlProfile::Svg));
+        let expected = Link::new(
+            FileLoc::dummy(),
+            Position { line, column },
+            "https://example.net/",
+        );
+        assert_eq!(vec![expected], result);
+    }
+
+    #[test_case(r#"<!-- <a href="https://example.net/">X</a> -->"#)]
+    #[test_case(r#"<![CDATA[ <a href="https://example.net/">X</a> ]]>"#)]
+    #[test_case(r#"<rect href="https://example.net/"/>"#)]
+    fn svg_no_links(input: &str) {
+        let result = aw_through_engine!(find_links(input, XmlProfile::Svg));
+        assert!(result.is_empty());
+    }
+
+    #[tokio::test]
+    async fn svg_anchors() {
+        let input = "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <g id=\"layer1\"/>\n</svg>";
+        let result = find_anchors(input, XmlProfile::Svg).await;
+        let expected = Anchor {
+            source: Locator {
+                file: FileLoc::dummy(),
+                pos: Position {
+                    line: 2,
+                    column: 10,
+                },
+            },
+            name: "layer1".to_owned(),
+            r#type: anchor::Type::ElementId,
+        };
+        assert_eq!(vec![expected], result);
+    }
+
+    #[tokio::test]
+    async fn docbook() {
+        let input = r#"<section xml:id="intro">
+  <para>See <xref linkend="setup"/> and <ulink url="https://example.net/a?b=1&amp;c=2">this</ulink>.</para>
+</section>"#;
+        let targets: Vec<String> = find_links(input, XmlProfile::DocBook)
+            .await
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(vec!["#setup", "https://example.net/a?b=1&c=2"], targets);
+        let anchors: Vec<String> = find_anchors(input, XmlProfile::DocBook)
+            .await
+            .into_iter()
+            .map(|anchor| anchor.name)
+            .collect();
+        assert_eq!(vec!["intro"], anchors);
+    }
+
+    #[tokio::test]
+    async fn dita() {
+        let input =
+            r#"<topic id="t1"><p conref="common.dita#c/p1"/><xref href="other.dita#t2"/></topic>"#;
+        let targets: Vec<String> = find_links(input, XmlProfile::Dita)
+            .await
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(vec!["common.dita#c/p1", "other.dita#t2"], targets);
+    }
+
+    #[tokio::test]
+    async fn generic() {
+        let input = r#"<doc><a xlink:href="a.svg"/><xref linkend="b"/><xref href="c.dita"/></doc>"#;
+        let targets: Vec<String> = find_links(input, XmlProfile::Generic)
+            .await
+            .iter()
+            .map(|link| link.target.to_string())
+            .collect();
+        assert_eq!(vec!["a.svg", "#b", "c.dita"], targets);
+    }
+}
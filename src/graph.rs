@@ -0,0 +1,344 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A document-level directed graph of the extracted links:
+//! Nodes are markup files and external hosts,
+//! edges are all the links from one node to an other,
+//! with their count and the fragments they point to.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use cli_utils::path_buf::PathBuf;
+use relative_path::RelativePathBuf;
+use serde::Serialize;
+
+use crate::link::{FileLoc, FileSystemLoc, Link, Target};
+
+/// What a node of the graph represents.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NodeKind {
+    /// A (markup) file, local or remote
+    Document,
+    /// An external host, representing all the remote targets on it
+    Host,
+}
+
+impl fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Document => write!(f, "document"),
+            Self::Host => write!(f, "host"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Serialize)]
+pub struct Node {
+    /// The normalized path or URL of a document, or the name of a host
+    pub id: String,
+    pub kind: NodeKind,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Serialize)]
+pub struct Edge {
+    /// The id of the node the links were found in
+    pub source: String,
+    /// The id of the node the links point to
+    pub target: String,
+    /// How many links there are from `source` to `target`
+    pub count: usize,
+    /// The distinct fragments the links point to, sorted
+    pub fragments: BTreeSet<String>,
+}
+
+/// The links between documents and hosts,
+/// with nodes sorted by id, and edges by source and target.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+/// The id of the document node of a markup file.
+/// Local paths are normalized lexically,
+/// so `./docs/../README.md` and `README.md` end up as the same node.
+#[must_use]
+pub fn document_id(file: &FileLoc) -> String {
+    match file {
+        FileLoc::System(path) => path.normalized().to_string(),
+        FileLoc::GitRev { rev, path } => FileLoc::GitRev {
+            rev: rev.clone(),
+            path: path.normalized(),
+        }
+        .to_string(),
+        FileLoc::Url(_) | FileLoc::Archive { .. } => file.to_string(),
+    }
+}
+
+/// The directory relative locations are resolved against
+/// when matching link targets to documents:
+/// the working directory, as for [`crate::config::Extractor::canonical_targets`].
+fn resolve_base() -> PathBuf {
+    std::env::current_dir().map_or_else(
+        |err| {
+            log::warn!("Failed to get the working directory to resolve targets: {err}");
+            PathBuf::new()
+        },
+        PathBuf::from,
+    )
+}
+
+/// Makes `path` absolute against `base` and normalizes it lexically,
+/// then makes it relative to `base` again if it lies within it.
+fn resolve_path(path: &FileSystemLoc, base: &PathBuf) -> FileSystemLoc {
+    let joined = match path {
+        FileSystemLoc::Relative(rel_path) => base.join(rel_path.as_str()),
+        FileSystemLoc::Absolute(abs_path) => abs_path.clone(),
+    };
+    match FileSystemLoc::Absolute(joined).normalized() {
+        FileSystemLoc::Absolute(abs_path) => abs_path
+            .strip_prefix(base)
+            .ok()
+            .and_then(|rel_path| RelativePathBuf::from_path(rel_path).ok())
+            .map_or(FileSystemLoc::Absolute(abs_path), FileSystemLoc::Relative),
+        relative @ FileSystemLoc::Relative(_) => relative,
+    }
+}
+
+/// The id under which a markup file is matched against link targets.
+///
+/// Its path is resolved against `base`,
+/// so `./docs/../README.md`, `README.md` and `$PWD/README.md`
+/// all end up with the same id,
+/// while `/README.md` is a different file.
+#[must_use]
+pub fn resolved_document_id(file: &FileLoc, base: &PathBuf) -> String {
+    match file {
+        FileLoc::System(path) => resolve_path(path, base).to_string(),
+        FileLoc::GitRev { rev, path } => FileLoc::GitRev {
+            rev: rev.clone(),
+            path: resolve_path(path, base),
+        }
+        .to_string(),
+        FileLoc::Archive { archive, path } => FileLoc::Archive {
+            archive: resolve_path(archive, base),
+            path: path.normalize(),
+        }
+        .to_string(),
+        FileLoc::Url(_) => file.to_string(),
+    }
+}
+
+/// The [`resolved_document_id`] of the document a link points to,
+/// if it is a local file.
+///
+/// Local targets are resolved with [`Target::canonical`],
+/// thus absolute paths are taken as they are,
+/// and relative ones are joined to the directory of the file they were found in.
+/// Links to fragments within the same document
+/// point to the document they were found in.
+#[must_use]
+pub fn target_document_id(link: &Link, base: &PathBuf) -> Option<String> {
+    match &link.target {
+        Target::FileSystem(fs_target) => {
+            let raw_path = fs_target.file.get_raw();
+            if raw_path.is_empty() {
+                return Some(resolved_document_id(&link.source.file, base));
+            }
+            let target_file = match link.source.file.as_ref() {
+                FileLoc::System(_) | FileLoc::GitRev { .. } => {
+                    let canonical = link
+                        .target
+                        .canonical(false, link.source.file.clone(), base)
+                        .ok()?;
+                    let Target::FileSystem(canonical_target) = canonical.as_ref() else {
+                        return None;
+                    };
+                    match link.source.file.as_ref() {
+                        FileLoc::GitRev { rev, .. } => FileLoc::GitRev {
+                            rev: rev.clone(),
+                            path: canonical_target.file.clone(),
+                        },
+                        _ => FileLoc::System(canonical_target.file.clone()),
+                    }
+                }
+                // NOTE URLs and paths within archives
+                //      resolve root-relative paths themselves.
+                FileLoc::Url(_) | FileLoc::Archive { .. } => {
+                    link.source.file.parent()?.join(&raw_path).ok()?
+                }
+            };
+            Some(resolved_document_id(&target_file, base))
+        }
+        Target::FileUrl(url) => Some(resolved_document_id(
+            &FileLoc::System(FileSystemLoc::Absolute(url.to_file_path().ok()?.into())),
+            base,
+        )),
+        _ => None,
+    }
+}
+
+/// Known markup files, to match link targets against.
+pub(crate) struct Documents {
+    base: PathBuf,
+    /// The [`document_id`]s of the files, by their [`resolved_document_id`]s
+    ids: HashMap<String, String>,
+}
+
+impl Documents {
+    pub(crate) fn new<'a>(files: impl IntoIterator<Item = &'a FileLoc>) -> Self {
+        let base = resolve_base();
+        let ids = files
+            .into_iter()
+            .map(|file| (resolved_document_id(file, &base), document_id(file)))
+            .collect();
+        Self { base, ids }
+    }
+
+    /// The id of the document a link points to, if it is a local file:
+    /// the [`document_id`] of a known file,
+    /// else its [`target_document_id`].
+    pub(crate) fn target_id(&self, link: &Link) -> Option<String> {
+        let resolved = target_document_id(link, &self.base)?;
+        Some(self.ids.get(&resolved).cloned().unwrap_or(resolved))
+    }
+}
+
+/// The node a link points to.
+/// Returns `None` for invalid targets,
+/// links to fragments within the same document,
+/// and remote targets without a host.
+fn target_node(link: &Link, documents: &Documents) -> Option<Node> {
+    match &link.target {
+        Target::FileSystem(fs_target) if fs_target.file.get_raw().is_empty() => None,
+        Target::FileSystem(_) | Target::FileUrl(_) => Some(Node {
+            id: documents.target_id(link)?,
+            kind: NodeKind::Document,
+        }),
+        Target::Invalid(_) => None,
+        _ => Some(Node {
+            id: link.target.host()?.to_owned(),
+            kind: NodeKind::Host,
+        }),
+    }
+}
+
+impl Graph {
+    /// Aggregates links into a graph.
+    /// Every markup file that contains a link becomes a node,
+    /// even if none of its links end up as an edge.
+    #[must_use]
+    pub fn from_links(links: &[Link]) -> Self {
+        let mut nodes: BTreeMap<String, NodeKind> = BTreeMap::new();
+        let mut edges: BTreeMap<(String, String), (usize, BTreeSet<String>)> = BTreeMap::new();
+        let documents = Documents::new(links.iter().map(|link| link.source.file.as_ref()));
+        for link in links {
+            let source = document_id(&link.source.file);
+            nodes.insert(source.clone(), NodeKind::Document);
+            let Some(target) = target_node(link, &documents) else {
+                continue;
+            };
+            nodes.entry(target.id.clone()).or_insert(target.kind);
+            let edge = edges.entry((source, target.id)).or_default();
+            edge.0 += 1;
+            if let Some(fragment) = link.target.fragment().filter(|frag| !frag.is_empty()) {
+                edge.1.insert(fragment.to_owned());
+            }
+        }
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|(id, kind)| Node { id, kind })
+                .collect(),
+            edges: edges
+                .into_iter()
+                .map(|((source, target), (count, fragments))| Edge {
+                    source,
+                    target,
+                    count,
+                    fragments,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::Position;
+    use std::sync::Arc;
+
+    fn link(file: &str, target: &str) -> Link {
+        let source = Arc::new(FileLoc::System(file.parse().expect("Valid path")));
+        Link::new(source, Position { line: 1, column: 1 }, target)
+    }
+
+    #[test]
+    fn node_kind_names() {
+        for value in [NodeKind::Document, NodeKind::Host] {
+            assert_eq!(
+                serde_json::to_string(&value).expect("Serializable"),
+                format!("\"{value}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn target_of_remote_source() {
+        let source = Arc::new(FileLoc::Url(
+            "https://example.org/docs/a.md".parse().expect("Valid URL"),
+        ));
+        let remote_link = Link::new(source, Position { line: 1, column: 1 }, "b.md");
+        assert_eq!(
+            target_document_id(&remote_link, &PathBuf::from("/work")).as_deref(),
+            Some("https://example.org/docs/b.md")
+        );
+    }
+
+    #[test]
+    fn target_of_absolute_path() {
+        let base = PathBuf::from("/work");
+        assert_eq!(
+            target_document_id(&link("docs/a.md", "/docs/b.md#x"), &base).as_deref(),
+            Some("/docs/b.md")
+        );
+        assert_eq!(
+            target_document_id(&link("docs/a.md", "/work/docs/../b.md"), &base).as_deref(),
+            Some("b.md")
+        );
+        assert_eq!(
+            target_document_id(&link("/work/docs/a.md", "../b.md"), &base).as_deref(),
+            Some("b.md")
+        );
+    }
+
+    #[test]
+    fn from_links() {
+        let links = [
+            link("./README.md", "docs/setup.md#install"),
+            link("README.md", "./docs/setup.md#usage"),
+            link("README.md", "#intro"),
+            link("docs/setup.md", "../README.md"),
+            link("docs/setup.md", "https://example.org/a"),
+            link("docs/setup.md", "https://example.org/b#c"),
+        ];
+        let graph = Graph::from_links(&links);
+        let node_ids: Vec<_> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(node_ids, vec!["README.md", "docs/setup.md", "example.org"]);
+        assert_eq!(graph.nodes[2].kind, NodeKind::Host);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.edges[0].source, "README.md");
+        assert_eq!(graph.edges[0].target, "docs/setup.md");
+        assert_eq!(graph.edges[0].count, 2);
+        assert_eq!(
+            graph.edges[0].fragments.iter().collect::<Vec<_>>(),
+            vec!["install", "usage"]
+        );
+        assert_eq!(graph.edges[2].target, "example.org");
+        assert_eq!(graph.edges[2].count, 2);
+    }
+}
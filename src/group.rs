@@ -0,0 +1,97 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Grouping of links by their canonical target,
+//! so each target has to be checked only once.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use cli_utils::path_buf::PathBuf;
+
+use crate::link::{Link, Locator, Target};
+
+/// All the links to one target.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Group {
+    /// The canonical target, without fragment
+    pub target: String,
+    /// The distinct fragments linked to, sorted
+    pub fragments: BTreeSet<String>,
+    /// Where the links were found, in the order they were extracted
+    pub sources: Vec<Locator>,
+}
+
+/// The canonical form of a target without its fragment,
+/// by which links are grouped.
+fn group_key(link: &Link, base: &PathBuf) -> String {
+    let canonical = link
+        .target
+        .canonical(false, link.source.file.clone(), base)
+        .unwrap_or_else(|err| {
+            log::warn!("Failed to canonicalize link target of {link}: {err}");
+            std::borrow::Cow::Borrowed(&link.target)
+        });
+    match canonical.without_fragment().as_ref() {
+        Target::FileSystem(fs_target) => fs_target.file.normalized().to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Groups `links` by their canonical target without fragment,
+/// sorted by target.
+/// Relative targets are resolved against the working directory,
+/// see [`Target::canonical`].
+#[must_use]
+pub fn by_target(links: &[Link]) -> Vec<Group> {
+    let base = std::env::current_dir()
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let mut groups: BTreeMap<String, Group> = BTreeMap::new();
+    for link in links {
+        let key = group_key(link, &base);
+        let group = groups.entry(key.clone()).or_insert_with(|| Group {
+            target: key,
+            fragments: BTreeSet::new(),
+            sources: vec![],
+        });
+        if let Some(fragment) = link.target.fragment().filter(|frag| !frag.is_empty()) {
+            group.fragments.insert(fragment.to_owned());
+        }
+        group.sources.push(link.source.clone());
+    }
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{FileLoc, Position};
+    use std::sync::Arc;
+
+    fn link(file: &str, target: &str) -> Link {
+        let source = Arc::new(FileLoc::System(file.parse().expect("Valid path")));
+        Link::new(source, Position { line: 1, column: 1 }, target)
+    }
+
+    #[test]
+    fn group() {
+        let links = [
+            link("README.md", "https://example.org/guide#install"),
+            link("docs/a.md", "https://example.org/guide#usage"),
+            link("docs/b.md", "https://example.org/guide"),
+            link("docs/a.md", "../README.md"),
+            link("docs/b.md", "../README.md#intro"),
+        ];
+        let groups = by_target(&links);
+        assert_eq!(groups.len(), 2);
+        assert!(groups[0].target.ends_with("README.md"));
+        assert_eq!(groups[0].sources.len(), 2);
+        assert_eq!(groups[1].target, "https://example.org/guide");
+        assert_eq!(groups[1].sources.len(), 3);
+        assert_eq!(
+            groups[1].fragments.iter().collect::<Vec<_>>(),
+            vec!["install", "usage"]
+        );
+    }
+}
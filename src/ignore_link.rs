@@ -3,6 +3,7 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use serde::{Deserialize, Deserializer, Serializer};
 use wildmatch::WildMatch;
 
 /// Parses the argument into a [`WildMatch`].
@@ -13,3 +14,47 @@ use wildmatch::WildMatch;
 pub fn parse(link_glob: &str) -> Result<WildMatch, String> {
     Ok(WildMatch::new(link_glob))
 }
+
+/// (De-)Serializes a list of [`WildMatch`]es as a list of plain glob strings,
+/// as they are written on the CLI.
+pub mod globs {
+    use super::{Deserialize, Deserializer, Serializer, WildMatch};
+
+    /// # Errors
+    ///
+    /// If the serializer fails.
+    pub fn serialize<S: Serializer>(globs: &[WildMatch], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(globs.iter().map(ToString::to_string))
+    }
+
+    /// # Errors
+    ///
+    /// If the input is not a list of strings.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<WildMatch>, D::Error> {
+        Ok(Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|glob| WildMatch::new(glob))
+            .collect())
+    }
+}
+
+/// (De-)Serializes a single [`WildMatch`] as a plain glob string.
+pub mod glob {
+    use super::{Deserialize, Deserializer, Serializer, WildMatch};
+
+    /// # Errors
+    ///
+    /// If the serializer fails.
+    pub fn serialize<S: Serializer>(glob: &WildMatch, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(glob)
+    }
+
+    /// # Errors
+    ///
+    /// If the input is not a string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<WildMatch, D::Error> {
+        Ok(WildMatch::new(&String::deserialize(deserializer)?))
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Book-keeping of which items were ignored by which ignore rule,
+//! to be able to spot rules that are no longer needed.
+
+use std::fmt;
+
+use serde::Serialize;
+use wildmatch::WildMatch;
+
+use crate::config::Extractor as Config;
+
+/// What an ignore rule applies to.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Kind {
+    /// See [`Config::ignore_links`]
+    Link,
+    /// See [`Config::ignore_anchors`]
+    Anchor,
+    /// See [`Config::ignore_files`]
+    File,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Link => write!(f, "link"),
+            Self::Anchor => write!(f, "anchor"),
+            Self::File => write!(f, "file"),
+        }
+    }
+}
+
+/// An ignore rule, together with what it ignored.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize)]
+pub struct Rule {
+    pub kind: Kind,
+    /// The glob as given in the configuration
+    pub glob: String,
+    /// The ignored items; links and anchors with their location
+    pub items: Vec<String>,
+}
+
+/// All the ignore rules, and what each of them ignored.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize)]
+pub struct Report {
+    pub rules: Vec<Rule>,
+}
+
+impl Report {
+    /// Creates a report containing all the ignore rules of the configuration,
+    /// including those of [`Config::overrides`],
+    /// none of which have ignored anything yet.
+    #[must_use]
+    pub fn new(conf: &Config) -> Self {
+        let mut report = Self::default();
+        let link_globs = conf.ignore_links.iter().chain(
+            conf.overrides
+                .iter()
+                .flat_map(|path_override| &path_override.ignore_links),
+        );
+        for (kind, glob) in link_globs
+            .map(|glob| (Kind::Link, glob))
+            .chain(conf.ignore_anchors.iter().map(|glob| (Kind::Anchor, glob)))
+            .chain(conf.ignore_files.iter().map(|glob| (Kind::File, glob)))
+        {
+            report.rule_mut(kind, glob.to_string());
+        }
+        report
+    }
+
+    fn rule_mut(&mut self, kind: Kind, glob: String) -> &mut Rule {
+        let idx = self
+            .rules
+            .iter()
+            .position(|rule| rule.kind == kind && rule.glob == glob)
+            .unwrap_or_else(|| {
+                self.rules.push(Rule {
+                    kind,
+                    glob,
+                    items: vec![],
+                });
+                self.rules.len() - 1
+            });
+        &mut self.rules[idx]
+    }
+
+    /// Notes that `item` was ignored by the rule `glob`.
+    pub fn record(&mut self, kind: Kind, glob: &WildMatch, item: String) {
+        self.rule_mut(kind, glob.to_string()).items.push(item);
+    }
+
+    /// Adds the rules and ignored items of an other report to this one.
+    pub fn merge(&mut self, other: Self) {
+        for other_rule in other.rules {
+            self.rule_mut(other_rule.kind, other_rule.glob)
+                .items
+                .extend(other_rule.items);
+        }
+    }
+
+    /// The rules that did not ignore anything.
+    pub fn unmatched(&self) -> impl Iterator<Item = &Rule> {
+        self.rules.iter().filter(|rule| rule.items.is_empty())
+    }
+
+    /// Logs a warning for each rule that did not ignore anything.
+    pub fn warn_unmatched(&self) {
+        for rule in self.unmatched() {
+            log::warn!(
+                "Ignore rule for {}s never matched anything: '{}'",
+                rule.kind,
+                rule.glob
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_names() {
+        for value in [Kind::Link, Kind::Anchor, Kind::File] {
+            assert_eq!(
+                serde_json::to_string(&value).expect("Serializable"),
+                format!("\"{value}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn record_and_merge() {
+        let conf = Config {
+            ignore_links: vec![WildMatch::new("https://*")],
+            ignore_anchors: vec![WildMatch::new("toc-*")],
+            ..Config::default()
+        };
+        let mut report = Report::new(&conf);
+        let mut file_report = Report::default();
+        file_report.record(
+            Kind::Link,
+            &WildMatch::new("https://*"),
+            "a.md:1:1:https://example.com".to_owned(),
+        );
+        report.merge(file_report);
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].items.len(), 1);
+        let unmatched: Vec<_> = report.unmatched().map(|rule| &rule.glob).collect();
+        assert_eq!(unmatched, vec!["toc-*"]);
+    }
+}
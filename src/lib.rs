@@ -4,24 +4,40 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 pub mod anchor;
+pub mod archive;
+pub mod backlinks;
+pub mod baseline;
 // This is here, because we want to share some of it
 // to tools that depend on us as a library
 // and reuse parts of our CLI, for example `mlc`.
 pub mod cli;
 pub mod config;
+pub mod diff;
+pub mod encoding;
 pub mod extractors;
+pub mod graph;
+pub mod group;
 pub mod ignore_link;
+pub mod ignored;
 pub mod link;
+pub mod link_filter;
 pub mod markup;
+pub mod orphans;
 pub mod result;
+pub mod source_code;
 pub mod state;
+pub mod summary;
+pub mod suppress;
+pub mod url_mapping;
+pub mod validate;
 
 use crate::anchor::Anchor;
-use crate::link::Link;
+use crate::link::{FileLoc, Link};
 pub use colored::*;
 pub use config::Extractor as Config;
 use git_version::git_version;
 use state::State;
+use std::sync::Arc;
 pub use wildmatch::WildMatch;
 
 // TODO Get rid of these two, replacing them with something more idiomatic (thiserror or error_set?)
@@ -38,26 +54,166 @@ pub const VERSION: &str = git_version!(cargo_prefix = "", fallback = "unknown");
 
 #[must_use]
 pub async fn find_all_links(conf: &Config) -> (Vec<Link>, Vec<Anchor>, Vec<BoxError>) {
-    let mut links = vec![];
-    let mut anchor_targets = vec![];
-    let mut errors: Vec<_> = vec![];
+    let extraction = extract(conf).await;
+    (extraction.links, extraction.anchors, extraction.errors)
+}
+
+/// Like [`find_all_links`],
+/// but additionally reports what each ignore rule has ignored.
+#[must_use]
+pub async fn find_all_links_and_ignored(
+    conf: &Config,
+) -> (Vec<Link>, Vec<Anchor>, Vec<BoxError>, ignored::Report) {
+    let extraction = extract(conf).await;
+    (
+        extraction.links,
+        extraction.anchors,
+        extraction.errors,
+        extraction.ignored,
+    )
+}
+
+/// Everything found in one run over the markup files.
+#[derive(Debug, Default)]
+pub struct Extraction {
+    pub links: Vec<Link>,
+    pub anchors: Vec<Anchor>,
+    pub errors: Vec<BoxError>,
+    /// What each ignore rule has ignored
+    pub ignored: ignored::Report,
+    /// The markup files that were scanned successfully, in order
+    pub scanned_files: Vec<Arc<FileLoc>>,
+    /// The lines of the scanned files that contain links;
+    /// only filled by [`extract_with_source_lines`]
+    pub source_lines: baseline::SourceLines,
+}
+
+/// Like [`find_all_links`],
+/// but additionally reports what each ignore rule has ignored,
+/// and which markup files were scanned.
+#[must_use]
+pub async fn extract(conf: &Config) -> Extraction {
+    extract_files(conf, false).await
+}
+
+/// Like [`extract`],
+/// but additionally keeps the lines of the scanned files that contain links,
+/// as needed for [`baseline::Fingerprint`]s.
+#[must_use]
+pub async fn extract_with_source_lines(conf: &Config) -> Extraction {
+    extract_files(conf, true).await
+}
+
+async fn extract_files(conf: &Config, keep_source_lines: bool) -> Extraction {
+    let mut extraction = Extraction {
+        ignored: ignored::Report::new(conf),
+        ..Extraction::default()
+    };
     for file in &conf.markup_files {
-        match markup::File::try_from(file.clone()) {
-            Ok(markup_file) => match extractors::gather_links(&markup_file, conf).await {
-                Ok(mut parsed) => {
-                    links.append(&mut parsed.links);
-                    anchor_targets.append(&mut parsed.anchors);
-                }
-                Err(err) => {
-                    errors.push(err.into());
+        let path = file.as_os_str().to_string_lossy();
+        if ignore_file(conf, &path, &mut extraction.ignored) {
+            continue;
+        }
+        let file_conf = conf.for_file(&path);
+        match markup::File::all_from_path(file.clone(), &file_conf).await {
+            Ok(markup_files) => {
+                for markup_file in markup_files {
+                    if ignore_file(
+                        conf,
+                        &markup_file.locator.to_string(),
+                        &mut extraction.ignored,
+                    ) {
+                        continue;
+                    }
+                    let gathered = if keep_source_lines {
+                        gather_links_and_lines(
+                            &markup_file,
+                            &file_conf,
+                            &mut extraction.source_lines,
+                        )
+                        .await
+                    } else {
+                        extractors::gather_links(&markup_file, &file_conf).await
+                    };
+                    match gathered {
+                        Ok(mut parsed) => {
+                            extraction.links.append(&mut parsed.links);
+                            extraction.anchors.append(&mut parsed.anchors);
+                            extraction.ignored.merge(parsed.ignored);
+                            extraction.scanned_files.push(markup_file.locator.clone());
+                        }
+                        Err(err) => {
+                            extraction.errors.push(err.into());
+                        }
+                    }
                 }
-            },
+            }
             Err(err) => {
-                errors.push(err.into());
+                extraction.errors.push(err.into());
             }
         }
     }
-    (links, anchor_targets, errors)
+    extraction
+}
+
+/// Like [`extractors::gather_links`],
+/// but additionally records the lines of `file` in `source_lines`,
+/// if it contains links.
+/// The content is fetched only once,
+/// as stdin can not be read twice,
+/// and downloading or extracting it again would be wasteful.
+async fn gather_links_and_lines(
+    file: &markup::File<'_>,
+    conf: &Config,
+    source_lines: &mut baseline::SourceLines,
+) -> std::io::Result<extractors::ParseRes> {
+    let content = file.fetch().await?;
+    let in_memory = markup::File {
+        markup_type: file.markup_type,
+        locator: file.locator.clone(),
+        content: markup::Content::InMemory(&content),
+        start: file.start.clone(),
+        default_encoding: file.default_encoding,
+    };
+    let parsed = extractors::gather_links(&in_memory, conf).await?;
+    if !parsed.links.is_empty() {
+        source_lines.insert(
+            file.locator.clone(),
+            content.lines().map(ToOwned::to_owned).collect(),
+        );
+    }
+    Ok(parsed)
+}
+
+/// Whether the markup file at `path` matches any of [`Config::ignore_files`],
+/// recording it in `ignored` if so.
+fn ignore_file(conf: &Config, path: &str, ignored: &mut ignored::Report) -> bool {
+    let rel_path = path.strip_prefix("./").unwrap_or(path);
+    let Some(file_ignorer) = conf
+        .ignore_files
+        .iter()
+        .find(|file_ignorer| file_ignorer.matches(rel_path))
+    else {
+        return false;
+    };
+    log::debug!("Ignoring markup file '{path}'");
+    ignored.record(ignored::Kind::File, file_ignorer, path.to_owned());
+    true
+}
+
+/// Reports the changes between the `old` and the `new` side of a diff,
+/// see [`diff::Items::load`].
+async fn run_diff(
+    config: &config::Tool,
+    old: &cli_utils::path_buf::PathBuf,
+    new: &cli_utils::path_buf::PathBuf,
+) -> BoxResult<()> {
+    let old_items = diff::Items::load(old, &config.extractor).await?;
+    let new_items = diff::Items::load(new, &config.extractor).await?;
+    let links = diff::compare(&old_items.links, &new_items.links);
+    let anchors = diff::compare(&old_items.anchors, &new_items.anchors);
+    result::sink_diff(config, &links, &anchors).await?;
+    Ok(())
 }
 
 /// Runs the markup link extractor.
@@ -65,11 +221,106 @@ pub async fn find_all_links(conf: &Config) -> (Vec<Link>, Vec<Anchor>, Vec<BoxEr
 ///
 /// # Errors
 ///
-/// If reading of any input or writing of the log or result-file failed.
+/// If reading of any input or writing of the log or result-file failed,
+/// or if validation is enabled and found broken links
+/// that are not in the baseline, and no baseline is being written.
 pub async fn run(state: &mut State) -> BoxResult<()> {
-    let (links, anchors, errors) = find_all_links(&state.config.extractor).await;
+    if let (Some(old), Some(new)) = (&state.config.diff_old, &state.config.diff_new) {
+        return run_diff(&state.config, old, new).await;
+    }
+    if let Some(remote_cache) = state.config.remote_cache.clone() {
+        state.load_remote_cache(remote_cache.as_ref()).await?;
+    }
+    let validating = state.config.validation.is_some();
+    let finding_orphans = state.config.orphans.is_some();
+    let summarizing = state.config.summary.is_some();
+    let fingerprinting = state.config.baseline.is_some() || state.config.write_baseline.is_some();
+    let needs_anchors = validating || summarizing || state.config.ignored.is_some();
+    let needs_links = needs_anchors
+        || finding_orphans
+        || state.config.backlinks.is_some()
+        || state.config.graph.is_some()
+        || state.config.write_baseline.is_some();
+    let mut extractor_conf = state.config.extractor.clone();
+    if needs_links {
+        // Validation, the summary and the ignored report need all links and anchors,
+        // and the other reports all links,
+        // whether they are to be reported or not.
+        extractor_conf.links = true;
+        extractor_conf.anchors |= needs_anchors;
+    }
+    let Extraction {
+        links,
+        anchors,
+        errors,
+        ignored,
+        scanned_files,
+        source_lines,
+    } = if fingerprinting {
+        extract_with_source_lines(&extractor_conf).await
+    } else {
+        extract(&extractor_conf).await
+    };
+    if state.config.ignored.is_some() {
+        ignored.warn_unmatched();
+        result::sink_ignored(&state.config, &ignored).await?;
+    }
+    if finding_orphans {
+        let report = orphans::find(&scanned_files, &links, &state.config.entry_points);
+        result::sink_orphans(&state.config, &report).await?;
+    }
+    if summarizing {
+        let summary = summary::Summary::new(&scanned_files, &links, &anchors, errors.len());
+        result::sink_summary(&state.config, &summary).await?;
+    }
+    if state.config.backlinks.is_some() {
+        result::sink_backlinks(&state.config, &backlinks::index(&links)).await?;
+    }
+    result::sink_graph(&state.config, &links).await?;
+    let mut broken_links = if validating {
+        validate::validate(&links, &anchors, &scanned_files).await?
+    } else {
+        vec![]
+    };
+    if let Some(baseline_file) = &state.config.write_baseline {
+        baseline::Baseline::new(&links, &broken_links, &source_lines)
+            .save(baseline_file.as_ref())
+            .await?;
+    }
+    let new_links = if let Some(baseline_file) = &state.config.baseline {
+        let mut known = baseline::Baseline::load(baseline_file.as_ref()).await?;
+        broken_links = known.new_broken_links(&broken_links, &source_lines);
+        Some(known.new_links(&links, &source_lines))
+    } else {
+        None
+    };
+    if validating {
+        result::sink_broken(&state.config, &broken_links).await?;
+    }
+    let reported_links = if state.config.links.is_some() {
+        new_links.as_deref().unwrap_or(&links)
+    } else {
+        &[]
+    };
+    let reported_anchors = if state.config.anchors.is_some() {
+        anchors.as_slice()
+    } else {
+        &[]
+    };
     // TODO make this more stream-like, where each found link is directly sent to all output streams/files. See repvar code for how to do that.
-    result::sink(&state.config, &links, &anchors, &errors)
-        .await
-        .map_err(Into::into)
+    result::sink_with_remote_cache(
+        &state.config,
+        reported_links,
+        reported_anchors,
+        &errors,
+        &state.remote_cache,
+    )
+    .await?;
+    if let Some(remote_cache) = &state.config.remote_cache {
+        state.save_remote_cache(remote_cache.as_ref()).await?;
+    }
+    if !broken_links.is_empty() && state.config.write_baseline.is_none() {
+        return Err(format!("{} broken local links found", broken_links.len()).into());
+    }
+    Ok(())
 }
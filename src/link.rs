@@ -3,10 +3,12 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use clap::{ValueEnum, builder::PossibleValue};
 use cli_utils::BoxResult;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
 use std::borrow::Cow;
 use std::ops::{Add, Sub};
+use std::path::Component;
 use std::sync::Arc;
 use std::{convert::Infallible, fmt, str::FromStr};
 
@@ -34,6 +36,20 @@ pub enum FileSystemLoc {
 pub enum FileLoc {
     Url(Url),
     System(FileSystemLoc),
+    /// A file as it is stored in a revision of a local git repository
+    GitRev {
+        /// The git revision, e.g. a tag, branch or commit hash
+        rev: String,
+        /// The path of the file, as it would be in the working tree
+        path: FileSystemLoc,
+    },
+    /// A file within an archive (e.g. a zip file)
+    Archive {
+        /// The location of the archive itself
+        archive: FileSystemLoc,
+        /// The path of the file within the archive
+        path: RelativePathBuf,
+    },
 }
 
 /// Where a link points to
@@ -41,6 +57,8 @@ pub enum FileLoc {
 pub enum Target {
     Http(Url),
     Ftp(Url),
+    /// A page in [Geminispace](https://geminiprotocol.net/)
+    Gemini(Url),
     EMail(Url), // ... yeees, "mailto:..." is a valid URI!
     FileUrl(Url),
     FileSystem(FileSystemTarget),
@@ -48,6 +66,83 @@ pub enum Target {
     Invalid(String),
 }
 
+/// The kind of a [`Target`], without its value.
+///
+/// It (de-)serializes as [`Self::as_str`],
+/// the same as it is given on the command line.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TargetKind {
+    Http,
+    Ftp,
+    Gemini,
+    EMail,
+    FileUrl,
+    FileSystem,
+    UnknownUrlSchema,
+    Invalid,
+}
+
+impl ValueEnum for TargetKind {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Http,
+            Self::Ftp,
+            Self::Gemini,
+            Self::EMail,
+            Self::FileUrl,
+            Self::FileSystem,
+            Self::UnknownUrlSchema,
+            Self::Invalid,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(self.as_str().into())
+    }
+}
+
+impl TargetKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Ftp => "ftp",
+            Self::Gemini => "gemini",
+            Self::EMail => "email",
+            Self::FileUrl => "file-url",
+            Self::FileSystem => "file-system",
+            Self::UnknownUrlSchema => "unknown-url-schema",
+            Self::Invalid => "invalid",
+        }
+    }
+}
+
+impl FromStr for TargetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::value_variants()
+            .iter()
+            .copied()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Unknown link target kind: '{s}'"))
+    }
+}
+
+impl Serialize for TargetKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TargetKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
 /// Where in the markup content (file/stream/string)
 /// a piece of content (e.g. a link) was found.
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
@@ -161,10 +256,31 @@ impl FileLoc {
         Ok(match self {
             Self::Url(base_url) => Self::Url(base_url.join(relative_path)?),
             Self::System(base_path) => Self::System(base_path.join(relative_path)?),
+            Self::GitRev { rev, path } => Self::GitRev {
+                rev: rev.clone(),
+                path: path.join(relative_path)?,
+            },
+            Self::Archive { archive, path } => Self::Archive {
+                archive: archive.clone(),
+                path: Self::join_in_archive(path, relative_path),
+            },
         })
     }
 
+    /// Joins a path within an archive,
+    /// treating the archive as the root of the namespace,
+    /// thus a leading `/` leads to the archives root.
+    fn join_in_archive(base: &RelativePathBuf, relative_path: &str) -> RelativePathBuf {
+        relative_path.strip_prefix('/').map_or_else(
+            || base.join_normalized(relative_path),
+            RelativePathBuf::from,
+        )
+    }
+
     /// Returns the `Path` without its final component, if there is one.
+    /// For a URL, this is the directory it is in, with a trailing slash,
+    /// so relative links can be joined onto it,
+    /// e.g. `https://example.org/docs/` for `https://example.org/docs/a.md`.
     ///
     /// Returns [`None`] if the path terminates in a root or prefix.
     ///
@@ -176,21 +292,24 @@ impl FileLoc {
     #[must_use]
     pub fn parent(&self) -> Option<Self> {
         Some(match self {
-            Self::Url(url) => {
-                let mut parent_url = url.clone();
-                parent_url.set_path(
-                    &PathBuf::from(url.path())
-                        .parent()
-                        .expect("There always has to be at least one source file path part")
-                        .to_string_lossy(),
-                );
-                Self::Url(parent_url)
-            }
+            // NOTE The trailing slash is significant for joining,
+            //      so we use this instead of manipulating the path.
+            Self::Url(url) => Self::Url(url.join("./").ok()?),
             Self::System(file_system_loc) => Self::System(
                 file_system_loc
                     .parent()
                     .expect("There always has to be at least one source file path part"),
             ),
+            Self::GitRev { rev, path } => Self::GitRev {
+                rev: rev.clone(),
+                path: path
+                    .parent()
+                    .expect("There always has to be at least one source file path part"),
+            },
+            Self::Archive { archive, path } => Self::Archive {
+                archive: archive.clone(),
+                path: path.parent().map(ToOwned::to_owned).unwrap_or_default(),
+            },
         })
     }
 
@@ -202,15 +321,26 @@ impl FileLoc {
     ///
     /// - never
     pub fn canonical(self: Arc<Self>, base: &PathBuf) -> BoxResult<Arc<Self>> {
-        Ok(
-            if let Self::System(FileSystemLoc::Relative(rel_source_path)) = &self.as_ref() {
-                Arc::new(Self::System(FileSystemLoc::Absolute(
-                    base.join(rel_source_path.as_str()),
-                )))
-            } else {
-                self
-            },
-        )
+        Ok(match self.as_ref() {
+            Self::System(FileSystemLoc::Relative(rel_source_path)) => Arc::new(Self::System(
+                FileSystemLoc::Absolute(base.join(rel_source_path.as_str())),
+            )),
+            Self::GitRev {
+                rev,
+                path: FileSystemLoc::Relative(rel_source_path),
+            } => Arc::new(Self::GitRev {
+                rev: rev.clone(),
+                path: FileSystemLoc::Absolute(base.join(rel_source_path.as_str())),
+            }),
+            Self::Archive {
+                archive: FileSystemLoc::Relative(rel_archive_path),
+                path,
+            } => Arc::new(Self::Archive {
+                archive: FileSystemLoc::Absolute(base.join(rel_archive_path.as_str())),
+                path: path.clone(),
+            }),
+            _ => self,
+        })
     }
 }
 
@@ -252,6 +382,10 @@ impl fmt::Display for FileLoc {
         match self {
             Self::Url(url) => url.fmt(f),
             Self::System(fs_loc) => fs_loc.fmt(f),
+            Self::GitRev { rev, path } => write!(f, "{rev}:{path}"),
+            Self::Archive { archive, path } => {
+                write!(f, "{archive}{}{path}", crate::archive::PATH_SEPARATOR)
+            }
         }
     }
 }
@@ -286,6 +420,8 @@ impl From<Url> for Target {
             Self::Http(url)
         } else if ["ftp", "sftp", "scp"].contains(&url.scheme()) {
             Self::Ftp(url)
+        } else if ["gemini"].contains(&url.scheme()) {
+            Self::Gemini(url)
         } else if ["mailto"].contains(&url.scheme()) {
             Self::EMail(url)
         } else if ["file"].contains(&url.scheme()) {
@@ -296,11 +432,75 @@ impl From<Url> for Target {
     }
 }
 
+impl Target {
+    /// Resolves a file-system target against a base URL,
+    /// the way an HTML `<base href="...">` element does.
+    /// Other targets, and those pointing only to an anchor
+    /// within the same document, are returned unchanged.
+    #[must_use]
+    pub fn with_base_url(self, base_url: &Url) -> Self {
+        let Self::FileSystem(fs_target) = &self else {
+            return self;
+        };
+        if fs_target.file.to_string().is_empty() {
+            return self;
+        }
+        base_url
+            .join(&fs_target.to_string())
+            .map_or(self, Self::from)
+    }
+}
+
+impl Target {
+    /// Re-roots a root-relative file-system target (e.g. `/docs/setup.md`)
+    /// onto the directory `site_root` (e.g. `public/docs/setup.md`).
+    /// Other targets are returned unchanged.
+    #[must_use]
+    pub fn with_site_root(self, site_root: &PathBuf) -> Self {
+        let Self::FileSystem(FileSystemTarget {
+            file: FileSystemLoc::Absolute(abs_path),
+            anchor,
+        }) = &self
+        else {
+            return self;
+        };
+        Self::FileSystem(FileSystemTarget {
+            file: FileSystemLoc::from(re_root(abs_path, site_root)).normalized(),
+            anchor: anchor.clone(),
+        })
+    }
+}
+
+/// Replaces the file-system root of `abs_path` with `new_root`.
+///
+/// # Panics
+///
+/// - Failed to extract the FS root from an absolute path
+/// - Failed to strip away the FS root from path
+///   from of which it was previously extracted
+fn re_root(abs_path: &PathBuf, new_root: &PathBuf) -> PathBuf {
+    // We need to remove the FS root from the absolute path first,
+    // in order to be able to re-root it
+    // (meaning: to replace the FS root with a directory).
+    // This way, the path is treated as relative by the `join` function,
+    // and appended to the new root,
+    // instead of leaving it as is, because it is already absolute.
+    let root = abs_path
+        .iter()
+        .next()
+        .expect("Absolute path needs to have at least a (first) root part");
+    let relativized_abs_path = abs_path
+        .strip_prefix(root)
+        .expect("To be able to strip root from path of which it was extracted from");
+    new_root.join(relativized_abs_path)
+}
+
 impl std::fmt::Display for Target {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Http(url)
             | Self::Ftp(url)
+            | Self::Gemini(url)
             | Self::EMail(url)
             | Self::FileUrl(url)
             | Self::UnknownUrlSchema(url) => write!(f, "{url}"),
@@ -311,6 +511,36 @@ impl std::fmt::Display for Target {
 }
 
 impl Target {
+    /// The kind of this target.
+    #[must_use]
+    pub const fn kind(&self) -> TargetKind {
+        match self {
+            Self::Http(_) => TargetKind::Http,
+            Self::Ftp(_) => TargetKind::Ftp,
+            Self::Gemini(_) => TargetKind::Gemini,
+            Self::EMail(_) => TargetKind::EMail,
+            Self::FileUrl(_) => TargetKind::FileUrl,
+            Self::FileSystem(_) => TargetKind::FileSystem,
+            Self::UnknownUrlSchema(_) => TargetKind::UnknownUrlSchema,
+            Self::Invalid(_) => TargetKind::Invalid,
+        }
+    }
+
+    /// The host of this target, if it is a URL with a host;
+    /// for e-mail addresses, this is the domain.
+    #[must_use]
+    pub fn host(&self) -> Option<&str> {
+        match self {
+            Self::Http(url)
+            | Self::Ftp(url)
+            | Self::Gemini(url)
+            | Self::FileUrl(url)
+            | Self::UnknownUrlSchema(url) => url.host_str(),
+            Self::EMail(url) => url.path().rsplit_once('@').map(|(_, domain)| domain),
+            Self::FileSystem(_) | Self::Invalid(_) => None,
+        }
+    }
+
     /// Whether this target definitely points to a local resource.
     /// Note: This is **not** the same as the inversion of `::is_remote()`!
     #[must_use]
@@ -318,6 +548,7 @@ impl Target {
         match self {
             Self::Http(_)
             | Self::Ftp(_)
+            | Self::Gemini(_)
             | Self::EMail(_)
             | Self::UnknownUrlSchema(_)
             | Self::Invalid(_) => false,
@@ -330,7 +561,7 @@ impl Target {
     #[must_use]
     pub const fn is_remote(&self) -> bool {
         match self {
-            Self::Http(_) | Self::Ftp(_) | Self::UnknownUrlSchema(_) => true,
+            Self::Http(_) | Self::Ftp(_) | Self::Gemini(_) | Self::UnknownUrlSchema(_) => true,
             Self::EMail(_) | Self::FileUrl(_) | Self::FileSystem(_) | Self::Invalid(_) => false,
         }
     }
@@ -347,6 +578,7 @@ impl Target {
         match self {
             Self::Http(_)
             | Self::Ftp(_)
+            | Self::Gemini(_)
             | Self::EMail(_)
             | Self::FileUrl(_)
             | Self::UnknownUrlSchema(_) => true,
@@ -361,9 +593,11 @@ impl Target {
     #[must_use]
     pub fn is_markup_content(&self) -> bool {
         match self {
-            Self::Http(url) | Self::Ftp(url) | Self::FileUrl(url) | Self::UnknownUrlSchema(url) => {
-                markup::Type::is_markup_url(url)
-            }
+            Self::Http(url)
+            | Self::Ftp(url)
+            | Self::Gemini(url)
+            | Self::FileUrl(url)
+            | Self::UnknownUrlSchema(url) => markup::Type::is_markup_url(url),
             Self::FileSystem(target) => target.file.is_markup(),
             Self::EMail(_) | Self::Invalid(_) => false,
         }
@@ -389,65 +623,74 @@ impl Target {
         source_file: Arc<FileLoc>,
         rel_path_base: &PathBuf, /*base: &FileLoc*/
     ) -> BoxResult<Cow<'_, Self>> {
-        if let Self::FileSystem(fs_target) = self {
-            match &fs_target.file {
-                FileSystemLoc::Absolute(orig_abs_path) => {
-                    if re_root_abs_paths {
-                        // We need to remove the FS root from the absolute path first,
-                        // in order to be able to re-root it
-                        // (meaning: to replace the FS root with a directory).
-                        // This way, the path is treated as relative by the `join` function,
-                        // and appended to the new root,
-                        // instead of leaving it as is, because it is already absolute.
-                        let root = orig_abs_path
-                            .iter()
-                            .next()
-                            .expect("Absolute path needs to have at least a (first) root part");
-                        let relativized_abs_path = orig_abs_path.strip_prefix(root).expect(
-                            "To be able to strip root from path of which it was extracted from",
-                        );
-                        return Ok(Cow::Owned(Self::FileSystem(FileSystemTarget {
-                            file: FileSystemLoc::Absolute(rel_path_base.join(relativized_abs_path)),
-                            anchor: fs_target.anchor.clone(),
-                        })));
-                    }
+        let Self::FileSystem(fs_target) = self else {
+            return Ok(Cow::Borrowed(self));
+        };
+        let source_path = match source_file.as_ref() {
+            FileLoc::Url(source_url) => {
+                // Both relative and root-relative paths
+                // are resolved against the URL of the page they were found in.
+                let mut abs_url = Self::from(source_url.join(fs_target.file.get_raw().as_ref())?);
+                abs_url.set_fragment(fs_target.anchor.clone());
+                return Ok(Cow::Owned(abs_url));
+            }
+            FileLoc::Archive { .. } => {
+                // Both relative and root-relative paths
+                // are resolved within the namespace of the archive.
+                // As there is no target type for files within archives,
+                // we use the same notation as for the source locator.
+                let abs_source_file = source_file.canonical(rel_path_base)?;
+                let base = abs_source_file.parent().ok_or_else(|| {
+                    format!("link source-file has no parent: '{abs_source_file}'")
+                })?;
+                let archive_loc = base.join(fs_target.file.get_raw().as_ref())?;
+                return Ok(Cow::Owned(Self::FileSystem(FileSystemTarget {
+                    file: FileSystemLoc::from_str(&archive_loc.to_string())?,
+                    anchor: fs_target.anchor.clone(),
+                })));
+            }
+            FileLoc::System(path) | FileLoc::GitRev { path, .. } => path,
+        };
+        match &fs_target.file {
+            FileSystemLoc::Absolute(orig_abs_path) => {
+                if re_root_abs_paths {
+                    return Ok(Cow::Owned(Self::FileSystem(FileSystemTarget {
+                        file: FileSystemLoc::Absolute(re_root(orig_abs_path, rel_path_base)),
+                        anchor: fs_target.anchor.clone(),
+                    })));
                 }
-                FileSystemLoc::Relative(relative_path) => {
-                    log::debug!(
-                        "Target::canonical - FileSystemLoc::Relative - relative_path: '{relative_path}'"
-                    );
-                    log::debug!(
-                        "Target::canonical - FileSystemLoc::Relative - source_file: '{source_file}'"
-                    );
-                    log::debug!(
-                        "Target::canonical - FileSystemLoc::Relative - rel_path_base: '{rel_path_base}'"
-                    );
-                    let base = source_file.canonical(rel_path_base)?;
-                    log::debug!("Target::canonical - FileSystemLoc::Relative - base 0: '{base}'");
-                    let base = base
-                        .parent()
-                        .ok_or_else(|| format!("link source-file has no parent: '{base}'"))?;
-                    log::debug!("Target::canonical - FileSystemLoc::Relative - base 1: '{base}'");
-                    match base.join(relative_path.as_str())? {
-                        FileLoc::Url(abs_url) => {
-                            let mut abs_url = Self::from(abs_url);
-                            abs_url.set_fragment(fs_target.anchor.clone());
-                            log::debug!(
-                                "Target::canonical - FileSystemLoc::Relative - FileLoc::Url - abs_url: '{abs_url}'"
-                            );
-                            return Ok(Cow::Owned(abs_url));
-                        }
-                        FileLoc::System(abs_path) => {
-                            log::debug!(
-                                "Target::canonical - FileSystemLoc::Relative - FileLoc::System - abs_url: '{abs_path}'"
-                            );
-                            return Ok(Cow::Owned(Self::FileSystem(FileSystemTarget {
-                                file: abs_path,
-                                anchor: fs_target.anchor.clone(),
-                            })));
-                        }
+            }
+            FileSystemLoc::Relative(relative_path) => {
+                log::debug!(
+                    "Target::canonical - FileSystemLoc::Relative - relative_path: '{relative_path}'"
+                );
+                log::debug!(
+                    "Target::canonical - FileSystemLoc::Relative - source_file: '{source_file}'"
+                );
+                log::debug!(
+                    "Target::canonical - FileSystemLoc::Relative - rel_path_base: '{rel_path_base}'"
+                );
+                let abs_source_path = match source_path {
+                    FileSystemLoc::Relative(rel_source_path) => {
+                        FileSystemLoc::Absolute(rel_path_base.join(rel_source_path.as_str()))
                     }
-                }
+                    FileSystemLoc::Absolute(_) => source_path.clone(),
+                };
+                log::debug!(
+                    "Target::canonical - FileSystemLoc::Relative - base 0: '{abs_source_path}'"
+                );
+                let base = abs_source_path.parent().ok_or_else(|| {
+                    format!("link source-file has no parent: '{abs_source_path}'")
+                })?;
+                log::debug!("Target::canonical - FileSystemLoc::Relative - base 1: '{base}'");
+                let abs_path = base.join(relative_path.as_str())?;
+                log::debug!(
+                    "Target::canonical - FileSystemLoc::Relative - FileLoc::System - abs_url: '{abs_path}'"
+                );
+                return Ok(Cow::Owned(Self::FileSystem(FileSystemTarget {
+                    file: abs_path,
+                    anchor: fs_target.anchor.clone(),
+                })));
             }
         }
         Ok(Cow::Borrowed(self))
@@ -458,7 +701,11 @@ impl Target {
     #[must_use]
     pub fn without_fragment(&self) -> Cow<'_, Self> {
         match self {
-            Self::Http(url) | Self::Ftp(url) | Self::FileUrl(url) | Self::UnknownUrlSchema(url)
+            Self::Http(url)
+            | Self::Ftp(url)
+            | Self::Gemini(url)
+            | Self::FileUrl(url)
+            | Self::UnknownUrlSchema(url)
                 if url.fragment().is_some() =>
             {
                 let mut no_frag = url.clone();
@@ -483,6 +730,7 @@ impl Target {
             Self::Http(url)
             | Self::EMail(url)
             | Self::Ftp(url)
+            | Self::Gemini(url)
             | Self::FileUrl(url)
             | Self::UnknownUrlSchema(url) => {
                 url.set_fragment(fragment.as_deref());
@@ -499,9 +747,11 @@ impl Target {
     #[must_use]
     pub fn fragment(&self) -> Option<&'_ str> {
         match self {
-            Self::Http(url) | Self::Ftp(url) | Self::FileUrl(url) | Self::UnknownUrlSchema(url) => {
-                url.fragment()
-            }
+            Self::Http(url)
+            | Self::Ftp(url)
+            | Self::Gemini(url)
+            | Self::FileUrl(url)
+            | Self::UnknownUrlSchema(url) => url.fragment(),
             Self::FileSystem(target) => target.anchor.as_deref(),
             Self::EMail(_) | Self::Invalid(_) => None,
         }
@@ -513,7 +763,7 @@ impl FileLoc {
     pub fn is_local(&self) -> bool {
         match self {
             Self::Url(url) => url.scheme() == "file",
-            Self::System(_) => true,
+            Self::System(_) | Self::GitRev { .. } | Self::Archive { .. } => true,
         }
     }
 
@@ -525,7 +775,10 @@ impl FileLoc {
     /// Whether this target is encoded as a file-system path.
     #[must_use]
     pub const fn is_file_system(&self) -> bool {
-        matches!(self, Self::System(_))
+        matches!(
+            self,
+            Self::System(_) | Self::GitRev { .. } | Self::Archive { .. }
+        )
     }
 
     /// Whether this target is encoded as a URL.
@@ -647,6 +900,33 @@ impl FileSystemLoc {
         })
     }
 
+    /// Resolves `./` and `../` parts of the path lexically,
+    /// without accessing the file-system.
+    /// Leading `../` parts of relative paths are kept.
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        match self {
+            Self::Relative(path) => Self::Relative(path.normalize()),
+            Self::Absolute(path) => {
+                let mut normalized = std::path::PathBuf::new();
+                for component in std::path::Path::new(path.as_os_str()).components() {
+                    match component {
+                        Component::CurDir => (),
+                        Component::ParentDir => match normalized.components().next_back() {
+                            Some(Component::Normal(_)) => {
+                                normalized.pop();
+                            }
+                            Some(Component::RootDir | Component::Prefix(_)) => (),
+                            _ => normalized.push(component),
+                        },
+                        _ => normalized.push(component),
+                    }
+                }
+                Self::Absolute(normalized.into())
+            }
+        }
+    }
+
     /// Returns the `Path` without its final component, if there is one.
     ///
     /// Returns [`None`] if the path terminates in a root or prefix.
@@ -822,3 +1102,43 @@ impl fmt::Display for Link {
         write!(f, "{}:{}", self.source, self.target)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url_parent(url: &str) -> String {
+        FileLoc::Url(Url::parse(url).expect("A valid URL"))
+            .parent()
+            .expect("A parent")
+            .to_string()
+    }
+
+    #[test]
+    fn parent_of_url() {
+        assert_eq!(
+            url_parent("https://example.org/docs/a.md"),
+            "https://example.org/docs/"
+        );
+        assert_eq!(
+            url_parent("https://example.org/docs/"),
+            "https://example.org/docs/"
+        );
+        assert_eq!(
+            url_parent("https://example.org/docs/a.md?lang=en#intro"),
+            "https://example.org/docs/"
+        );
+        assert_eq!(
+            url_parent("https://example.org/a.md"),
+            "https://example.org/"
+        );
+        let parent =
+            FileLoc::Url(Url::parse("https://example.org/docs/a.md").expect("A valid URL"))
+                .parent()
+                .expect("A parent");
+        assert_eq!(
+            parent.join("b.md").expect("Joining to succeed").to_string(),
+            "https://example.org/docs/b.md"
+        );
+    }
+}
@@ -0,0 +1,239 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Composable filters deciding which links to extract,
+//! beyond the simple wildcard globs of [`crate::ignore_link`].
+
+use clap::{ValueEnum, builder::PossibleValue};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+use std::str::FromStr;
+use wildmatch::WildMatch;
+
+use crate::link::{Target, TargetKind};
+
+/// Whether a link target is local or remote.
+///
+/// It (de-)serializes as [`Self::as_str`],
+/// the same as it is given on the command line.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Locality {
+    /// See [`Target::is_local`]
+    Local,
+    /// See [`Target::is_remote`]
+    Remote,
+}
+
+impl ValueEnum for Locality {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Local, Self::Remote]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(self.as_str().into())
+    }
+}
+
+impl Locality {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Remote => "remote",
+        }
+    }
+
+    #[must_use]
+    pub const fn matches(self, target: &Target) -> bool {
+        match self {
+            Self::Local => target.is_local(),
+            Self::Remote => target.is_remote(),
+        }
+    }
+}
+
+impl FromStr for Locality {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::value_variants()
+            .iter()
+            .copied()
+            .find(|locality| locality.as_str() == s)
+            .ok_or_else(|| format!("Unknown link target locality: '{s}'"))
+    }
+}
+
+impl Serialize for Locality {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Locality {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// A set of conditions on a link target.
+/// The rule matches if all of its set conditions do;
+/// a rule without any conditions matches every target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rule {
+    /// A regular expression searched for in the whole target
+    #[serde(with = "serde_regex")]
+    pub regex: Option<Regex>,
+    /// The target has to be of one of these kinds
+    pub kinds: Vec<TargetKind>,
+    /// The host of the target has to match one of these globs,
+    /// e.g. `*.example.com`
+    #[serde(with = "crate::ignore_link::globs")]
+    pub hosts: Vec<WildMatch>,
+    /// The target has to be of this locality
+    pub locality: Option<Locality>,
+}
+
+impl Rule {
+    /// Whether all the conditions of this rule match the target.
+    #[must_use]
+    pub fn matches(&self, target: &Target) -> bool {
+        self.regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(&target.to_string()))
+            && (self.kinds.is_empty() || self.kinds.contains(&target.kind()))
+            && (self.hosts.is_empty()
+                || target
+                    .host()
+                    .is_some_and(|host| self.hosts.iter().any(|glob| glob.matches(host))))
+            && self
+                .locality
+                .is_none_or(|locality| locality.matches(target))
+    }
+}
+
+/// Decides which links to extract.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkFilter {
+    /// If not empty, only links matching at least one of these are extracted
+    pub include: Vec<Rule>,
+    /// Links matching any of these are not extracted
+    pub exclude: Vec<Rule>,
+}
+
+impl LinkFilter {
+    /// Whether a link with this target is to be extracted.
+    #[must_use]
+    pub fn accepts(&self, target: &Target) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|rule| rule.matches(target)))
+            && !self.exclude.iter().any(|rule| rule.matches(target))
+    }
+
+    /// Whether a link is to be extracted,
+    /// given different forms of its target,
+    /// e.g. as written and as resolved:
+    /// It is if any form is included, and none is excluded.
+    #[must_use]
+    pub fn accepts_any(&self, targets: &[&Target]) -> bool {
+        let matches_any = |rule: &Rule| targets.iter().any(|target| rule.matches(target));
+        (self.include.is_empty() || self.include.iter().any(matches_any))
+            && !self.exclude.iter().any(matches_any)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepted(filter: &LinkFilter) -> Vec<&'static str> {
+        [
+            "https://example.com/a",
+            "https://docs.example.com/b",
+            "http://other.net/c.pdf",
+            "mailto:someone@example.com",
+            "local/file.md",
+        ]
+        .into_iter()
+        .filter(|target| filter.accepts(&Target::from(*target)))
+        .collect()
+    }
+
+    #[test]
+    fn any_form() {
+        let filter = LinkFilter {
+            include: vec![Rule {
+                kinds: vec![TargetKind::Http],
+                ..Rule::default()
+            }],
+            exclude: vec![Rule {
+                regex: Some(Regex::new(r"^/img/").expect("Valid regex")),
+                ..Rule::default()
+            }],
+        };
+        let resolved = Target::from("https://example.com/img/a.png");
+        assert!(filter.accepts_any(&[&Target::from("img/a.png"), &resolved]));
+        assert!(!filter.accepts_any(&[&Target::from("/img/a.png"), &resolved]));
+    }
+
+    #[test]
+    fn include_and_exclude() {
+        let filter = LinkFilter {
+            include: vec![Rule {
+                kinds: vec![TargetKind::Http],
+                ..Rule::default()
+            }],
+            exclude: vec![Rule {
+                regex: Some(Regex::new(r"\.pdf$").expect("Valid regex")),
+                ..Rule::default()
+            }],
+        };
+        assert_eq!(
+            accepted(&filter),
+            vec!["https://example.com/a", "https://docs.example.com/b"]
+        );
+    }
+
+    #[test]
+    fn hosts_and_locality() {
+        let filter = LinkFilter {
+            include: vec![],
+            exclude: vec![
+                Rule {
+                    hosts: vec![WildMatch::new("*example.com")],
+                    locality: Some(Locality::Remote),
+                    ..Rule::default()
+                },
+                Rule {
+                    locality: Some(Locality::Local),
+                    ..Rule::default()
+                },
+            ],
+        };
+        assert_eq!(
+            accepted(&filter),
+            vec!["http://other.net/c.pdf", "mailto:someone@example.com"]
+        );
+    }
+
+    #[test]
+    fn toml() {
+        let filter: LinkFilter = toml::from_str(
+            r#"
+[[exclude]]
+kinds = ["email"]
+
+[[include]]
+regex = "^https?://"
+hosts = ["docs.*"]
+locality = "remote"
+"#,
+        )
+        .expect("Parsing to succeed");
+        assert_eq!(accepted(&filter), vec!["https://docs.example.com/b"]);
+    }
+}
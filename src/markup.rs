@@ -9,15 +9,28 @@ use cli_utils::{
     ignore_path::IgnorePath,
     path_buf::PathBuf,
 };
-use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, ffi::OsStr, str::FromStr, sync::Arc};
+use encoding_rs::Encoding;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+use std::{
+    borrow::Cow,
+    ffi::OsStr,
+    fmt,
+    str::FromStr,
+    sync::{Arc, LazyLock},
+};
 use thiserror::Error;
 use url::Url;
+use wildmatch::WildMatch;
 
+use crate::archive;
+use crate::config::Extractor as Config;
+use crate::encoding;
 use crate::markup;
-use async_std::{fs, path::Path};
+use crate::source_code::Language;
+use async_std::{fs, io::ReadExt, path::Path, task};
 
-use crate::link::{FileLoc, Position};
+use crate::link::{FileLoc, FileSystemLoc, Position};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Content<'a> {
@@ -25,20 +38,110 @@ pub enum Content<'a> {
     LocalFile(PathBuf),
     /// stores the whole content of the file as a string
     InMemory(&'a str),
-    // Url(Url, &'a str)
+    /// the content is read from stdin;
+    /// NOTE This can only be fetched once!
+    Stdin,
+    /// stores the URL to download the content from
+    Url(Url),
+    /// stores the revision and the (working tree) path of a file
+    /// in a local git repository
+    GitBlob { rev: String, path: PathBuf },
+    /// stores the file-name of a single, gzip compressed file
+    Gzip(PathBuf),
+    /// stores the whole, raw content of the file,
+    /// e.g. when it was read from an archive
+    Buffer(Arc<[u8]>),
+}
+
+/// Downloads the content of a (remote) file.
+fn download(url: &Url) -> Result<Vec<u8>, std::io::Error> {
+    log::debug!("Downloading '{url}' ...");
+    ureq::get(url.as_str())
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_vec())
+        .map_err(|err| std::io::Error::other(format!("Failed to download '{url}': {err}")))
+}
+
+/// Reads the content of a file as it is stored in a git revision,
+/// without checking it out.
+fn read_git_blob(rev: &str, path: &PathBuf) -> Result<Vec<u8>, std::io::Error> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::other(format!("Missing file-name for path: '{path}'")))?;
+    // NOTE Running git in the files directory and using a `./` path,
+    //      makes this work with both relative and absolute paths.
+    let dir = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let object = format!("{rev}:./{}", file_name.to_string_lossy());
+    log::debug!("Reading git object '{object}' in '{}' ...", dir.display());
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir.as_os_str())
+        .args(["cat-file", "blob", &object])
+        .output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "Failed to read '{path}' at git revision '{rev}': {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
 }
 
 impl<'a> Content<'a> {
-    /// Returns the actual content as str
+    /// Returns the actual content as str,
+    /// decoding it as described in [`encoding::decode`],
+    /// without a default encoding.
     ///
     /// # Errors
-    /// If the content has to be read from a URL or the File-System,
+    /// If the content has to be read from a URL, stdin or the File-System,
     /// there might be an read error.
+    /// If it can not be decoded, the error is of kind
+    /// [`std::io::ErrorKind::InvalidData`], wrapping an [`encoding::DecodingError`].
     pub async fn fetch(&self) -> Result<Cow<'a, str>, std::io::Error> {
-        match self {
-            Self::LocalFile(file_name) => fs::read_to_string(file_name).await.map(Cow::Owned),
-            Self::InMemory(content) => Ok(Cow::Borrowed(content)),
-        }
+        self.fetch_with_default_encoding(None).await
+    }
+
+    /// Returns the actual content as str,
+    /// decoding it as described in [`encoding::decode`].
+    ///
+    /// # Errors
+    /// If the content has to be read from a URL, stdin or the File-System,
+    /// there might be an read error.
+    /// If it can not be decoded, the error is of kind
+    /// [`std::io::ErrorKind::InvalidData`], wrapping an [`encoding::DecodingError`].
+    pub async fn fetch_with_default_encoding(
+        &self,
+        default_encoding: Option<&'static Encoding>,
+    ) -> Result<Cow<'a, str>, std::io::Error> {
+        let raw = match self {
+            Self::InMemory(content) => return Ok(Cow::Borrowed(content)),
+            Self::LocalFile(file_name) => fs::read(file_name).await?,
+            Self::Stdin => {
+                let mut content = vec![];
+                async_std::io::stdin().read_to_end(&mut content).await?;
+                content
+            }
+            Self::Url(url) => {
+                let url_owned = url.clone();
+                task::spawn_blocking(move || download(&url_owned)).await?
+            }
+            Self::GitBlob { rev, path } => {
+                let rev_owned = rev.clone();
+                let path_owned = path.clone();
+                task::spawn_blocking(move || read_git_blob(&rev_owned, &path_owned)).await?
+            }
+            Self::Gzip(file_name) => {
+                let path_owned: std::path::PathBuf = file_name.as_path().into();
+                task::spawn_blocking(move || archive::decompress_gz(&path_owned)).await?
+            }
+            Self::Buffer(bytes) => bytes.to_vec(),
+        };
+        encoding::decode(raw, default_encoding)
+            .map(Cow::Owned)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
     }
 }
 
@@ -58,19 +161,75 @@ pub struct File<'a> {
     /// but in the case of e.g. in-line HTML in a Markdown file,
     /// this would commonly be a larger position.
     pub start: Position,
+    /// The encoding to decode the content with,
+    /// if it is neither UTF-8 nor declares its encoding.
+    pub default_encoding: Option<&'static Encoding>,
+}
+
+impl<'a> File<'a> {
+    /// Returns the actual content as str,
+    /// decoding it as described in [`encoding::decode`].
+    ///
+    /// # Errors
+    /// See [`Content::fetch_with_default_encoding`].
+    pub async fn fetch(&self) -> Result<Cow<'a, str>, std::io::Error> {
+        self.content
+            .fetch_with_default_encoding(self.default_encoding)
+            .await
+    }
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// The markup language of a file.
+///
+/// It (de-)serializes as [`Self::as_str`],
+/// the same as it is given on the command line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Type {
     #[default]
     Markdown,
     Html,
+    /// Markdown with embedded JSX and ESM (e.g. used by Docusaurus)
+    Mdx,
+    /// An XML based format, specified further by a profile
+    Xml(XmlProfile),
+    /// Plain text, in which we look for URLs
+    Plain,
+    /// Source code, in whose comments we look for URLs
+    Code,
+    /// Gemini gemtext, the native markup of Geminispace
+    Gemtext,
+}
+
+/// Which flavor of XML a markup file is written in;
+/// this defines which elements and attributes constitute links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmlProfile {
+    /// Scalable Vector Graphics;
+    /// links are `href`/`xlink:href` on `<a>`, `<image>`, `<use>` and the like
+    Svg,
+    /// `<xref linkend="..."/>`, `<link linkend="..."/>` and `<ulink url="..."/>`
+    DocBook,
+    /// `href` on `<xref>`, `<link>`, `<topicref>` and the like, and `conref`
+    Dita,
+    /// Any XML; recognizes all of the above
+    Generic,
 }
 
 // Can also be derived with feature flag `#[derive(ArgEnum)]`
 impl ValueEnum for Type {
     fn value_variants<'a>() -> &'a [Self] {
-        &[Self::Markdown, Self::Html]
+        &[
+            Self::Markdown,
+            Self::Html,
+            Self::Mdx,
+            Self::Xml(XmlProfile::Svg),
+            Self::Xml(XmlProfile::DocBook),
+            Self::Xml(XmlProfile::Dita),
+            Self::Xml(XmlProfile::Generic),
+            Self::Plain,
+            Self::Code,
+            Self::Gemtext,
+        ]
     }
 
     fn to_possible_value(&self) -> Option<PossibleValue> {
@@ -78,12 +237,96 @@ impl ValueEnum for Type {
     }
 }
 
+/// The names of extension-less files that are treated as plain text,
+/// compared case-insensitively
+const PLAIN_FILE_NAMES: &[&str] = &["LICENSE", "LICENCE", "COPYING", "NOTICE"];
+
+/// How many bytes from the start of a file are looked at
+/// when sniffing its markup type.
+const SNIFF_LEN: u64 = 8 * 1024;
+
+/// An ATX heading followed by a blank line (or the end of the content),
+/// which tells it apart from a single `# comment` line
+static SNIFF_MD_HEADING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^ {0,3}#{1,6}[ \t]+[^ \t\r\n].*\r?\n(?:[ \t]*\r?\n|\z)").unwrap()
+});
+static SNIFF_MD_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)\[[^\]\r\n]+\]\([^)\r\n]+\)|^ {0,3}\[[^\]\r\n]+\]:[ \t]+[^ \t\r\n]").unwrap()
+});
+
 #[derive(Debug, Error)]
 pub enum TypeExtractionError {
     #[error("File extension '{0}' does not match any supported markup type")]
     UnsupportedFileExt(String),
     #[error("File has no extension, so we can not determine markup type")]
     NoFileExt,
+    #[error("Failed to read the file for sniffing its markup type: {0}")]
+    Sniff(#[from] std::io::Error),
+    #[error("Reading from stdin requires an explicitly set markup type")]
+    StdinWithoutType,
+    #[error("Failed to read archive '{0}': {1}")]
+    Archive(String, std::io::Error),
+    #[error(
+        "Reading archive or compressed file '{0}' from a git revision is not supported; \
+check out that revision instead"
+    )]
+    ArchiveAtGitRev(String),
+}
+
+/// Forces all files whose path matches `glob` to be treated as `markup_type`,
+/// independent of their file extension.
+///
+/// On the CLI and in the configuration file,
+/// this is written as `GLOB=TYPE`, e.g. `*/README=md`.
+#[derive(Debug, Clone)]
+pub struct TypeOverride {
+    pub glob: WildMatch,
+    pub markup_type: Type,
+}
+
+impl TypeOverride {
+    /// Whether this override applies to the file with the given path.
+    /// Both the whole path and only the file name are matched against.
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        self.glob.matches(path) || self.glob.matches(file_name)
+    }
+}
+
+impl fmt::Display for TypeOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.glob, self.markup_type.as_str())
+    }
+}
+
+impl Serialize for TypeOverride {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeOverride {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for TypeOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (glob, markup_type) = s
+            .rsplit_once('=')
+            .ok_or_else(|| format!("Missing '=' in markup type override '{s}'; use GLOB=TYPE"))?;
+        Ok(Self {
+            glob: WildMatch::new(glob),
+            markup_type: <Type as FromStr>::from_str(markup_type)
+                .map_err(|err| format!("{err} in markup type override '{s}': '{markup_type}'"))?,
+        })
+    }
 }
 
 impl Type {
@@ -91,7 +334,7 @@ impl Type {
         let ext_opt = Self::get_extension_from_filename(file_name.as_ref());
         if let Some(ext) = ext_opt {
             let ext_lower = ext.to_lowercase();
-            log::warn!("Extracted file ext: {ext_lower}");
+            log::trace!("Extracted file ext: {ext_lower}");
             for t in Self::value_variants() {
                 for known_ext in t.file_extensions() {
                     if ext_lower == known_ext {
@@ -100,12 +343,203 @@ impl Type {
                 }
             }
             Err(TypeExtractionError::UnsupportedFileExt(ext.to_string()))
+        } else if Self::is_plain_file_name(file_name.as_ref()) {
+            Ok(Self::Plain)
         } else {
             Err(TypeExtractionError::NoFileExt)
         }
     }
 }
 
+impl Type {
+    /// Guesses the markup type from (the start of) the content of a file.
+    ///
+    /// Recognizes HTML by a doctype or an `<html>` tag,
+    /// and Markdown by ATX headings followed by a blank line, or links.
+    /// Scripts starting with a shebang (`#!`) are never Markdown,
+    /// as their `#` comments look like headings.
+    #[must_use]
+    pub fn sniff(content: &str) -> Option<Self> {
+        let start = content.trim_start_matches('\u{feff}').trim_start();
+        let start_lower = start
+            .get(..start.len().min(512))
+            .unwrap_or(start)
+            .to_lowercase();
+        if start_lower.starts_with("<!doctype html") || start_lower.contains("<html") {
+            Some(Self::Html)
+        } else if start.starts_with("#!") {
+            None
+        } else if SNIFF_MD_HEADING.is_match(content) || SNIFF_MD_LINK.is_match(content) {
+            Some(Self::Markdown)
+        } else {
+            None
+        }
+    }
+
+    /// Figures out the markup type of a file.
+    ///
+    /// The first matching type override is used, if any;
+    /// otherwise the file extension decides,
+    /// and if that fails and sniffing is enabled,
+    /// the start of the file content is analyzed.
+    ///
+    /// # Errors
+    ///
+    /// If no type could be determined,
+    /// or reading the file for sniffing failed.
+    pub async fn detect(path: &Path, conf: &Config) -> Result<Self, TypeExtractionError> {
+        let path_str = path.display().to_string();
+        if archive::Format::from_file_name(&path_str) == Some(archive::Format::Gz) {
+            let file_name = &path_str[..path_str.len() - ".gz".len()];
+            if !conf.sniff_content {
+                return Self::detect_by_name(&path_str, file_name, conf);
+            }
+            let content = Content::Gzip(path.to_path_buf().into()).fetch().await?;
+            return Self::detect_content(&path_str, file_name, &content, conf);
+        }
+        let err = match Self::detect_by_name(&path_str, &path_str, conf) {
+            Ok(markup_type) => return Ok(markup_type),
+            Err(err) => err,
+        };
+        if !conf.sniff_content {
+            return Err(err);
+        }
+        let mut head = vec![];
+        fs::File::open(path)
+            .await?
+            .take(SNIFF_LEN)
+            .read_to_end(&mut head)
+            .await?;
+        let sniffed = Self::sniff(&String::from_utf8_lossy(&head));
+        log::debug!("Sniffed markup type of '{path_str}': {sniffed:?}");
+        sniffed.ok_or(err)
+    }
+
+    /// Figures out the markup type of a remote file,
+    /// like [`Self::detect`] does for local files,
+    /// and returns it together with the content to extract from.
+    /// If sniffing is required, the whole file gets downloaded,
+    /// and that download is the returned content;
+    /// otherwise, the content is only downloaded when fetched.
+    ///
+    /// # Errors
+    ///
+    /// If no type could be determined,
+    /// or downloading the file for sniffing failed.
+    pub async fn detect_url(
+        url: &Url,
+        conf: &Config,
+    ) -> Result<(Self, Content<'static>), TypeExtractionError> {
+        let err = match Self::detect_by_name(url.as_str(), url.path(), conf) {
+            Ok(markup_type) => return Ok((markup_type, Content::Url(url.clone()))),
+            Err(err) => err,
+        };
+        if !conf.sniff_content {
+            return Err(err);
+        }
+        let url_owned = url.clone();
+        let raw = task::spawn_blocking(move || download(&url_owned)).await?;
+        let sniffed = Self::sniff(&String::from_utf8_lossy(&raw));
+        log::debug!("Sniffed markup type of '{url}': {sniffed:?}");
+        Ok((sniffed.ok_or(err)?, Content::Buffer(raw.into())))
+    }
+
+    /// Figures out the markup type of a file as stored in a git revision,
+    /// like [`Self::detect`] does for files in the working tree.
+    ///
+    /// # Errors
+    ///
+    /// If no type could be determined,
+    /// or reading the file for sniffing failed.
+    pub async fn detect_git_blob(
+        rev: &str,
+        path: &PathBuf,
+        conf: &Config,
+    ) -> Result<Self, TypeExtractionError> {
+        let path_str = path.display().to_string();
+        let err = match Self::detect_by_name(&path_str, &path_str, conf) {
+            Ok(markup_type) => return Ok(markup_type),
+            Err(err) => err,
+        };
+        if !conf.sniff_content {
+            return Err(err);
+        }
+        let content = Content::GitBlob {
+            rev: rev.to_owned(),
+            path: path.clone(),
+        }
+        .fetch()
+        .await?;
+        let sniffed = Self::sniff(&content);
+        log::debug!("Sniffed markup type of '{rev}:{path_str}': {sniffed:?}");
+        sniffed.ok_or(err)
+    }
+
+    /// Figures out the markup type of a file of which we have the content already,
+    /// like [`Self::detect`] does for local files.
+    ///
+    /// # Errors
+    ///
+    /// If no type could be determined.
+    pub fn detect_content(
+        locator: &str,
+        file_name: &str,
+        content: &str,
+        conf: &Config,
+    ) -> Result<Self, TypeExtractionError> {
+        let err = match Self::detect_by_name(locator, file_name, conf) {
+            Ok(markup_type) => return Ok(markup_type),
+            Err(err) => err,
+        };
+        if !conf.sniff_content {
+            return Err(err);
+        }
+        let sniffed = Self::sniff(content);
+        log::debug!("Sniffed markup type of '{locator}': {sniffed:?}");
+        sniffed.ok_or(err)
+    }
+
+    /// Whether `file_name` is one of [`PLAIN_FILE_NAMES`],
+    /// ignoring the directories it is in.
+    fn is_plain_file_name(file_name: &str) -> bool {
+        Path::new(file_name)
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|base_name| {
+                PLAIN_FILE_NAMES
+                    .iter()
+                    .any(|plain_name| base_name.eq_ignore_ascii_case(plain_name))
+            })
+    }
+
+    /// Figures out the markup type of a file without looking at its content:
+    /// The explicitly set type is used if any,
+    /// else the first type override matching `locator`,
+    /// else [`Self::Code`] if `file_name` is in one of the configured
+    /// [`Config::code_languages`],
+    /// else the one derived from the extension of `file_name`.
+    fn detect_by_name(
+        locator: &str,
+        file_name: &str,
+        conf: &Config,
+    ) -> Result<Self, TypeExtractionError> {
+        if let Some(markup_type) = conf.markup_type {
+            return Ok(markup_type);
+        }
+        if let Some(type_override) = conf
+            .type_overrides
+            .iter()
+            .find(|type_override| type_override.matches(locator))
+        {
+            return Ok(type_override.markup_type);
+        }
+        if Language::find_by_file_name(&conf.code_languages, file_name).is_some() {
+            return Ok(Self::Code);
+        }
+        Self::try_from_file_name(file_name)
+    }
+}
+
 impl TryFrom<&Path> for Type {
     type Error = TypeExtractionError;
 
@@ -122,11 +556,33 @@ impl FromStr for Type {
         match s {
             "md" => Ok(Self::Markdown),
             "html" => Ok(Self::Html),
+            "mdx" => Ok(Self::Mdx),
+            "svg" => Ok(Self::Xml(XmlProfile::Svg)),
+            "docbook" => Ok(Self::Xml(XmlProfile::DocBook)),
+            "dita" => Ok(Self::Xml(XmlProfile::Dita)),
+            "xml" => Ok(Self::Xml(XmlProfile::Generic)),
+            "plain" | "txt" => Ok(Self::Plain),
+            "code" => Ok(Self::Code),
+            "gmi" | "gemtext" => Ok(Self::Gemtext),
             _ => Err("Unknown markup file extension"),
         }
     }
 }
 
+impl Serialize for Type {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Type {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
 impl Type {
     fn get_extension_from_filename(file_name: &str) -> Option<&str> {
         Path::new(file_name).extension().and_then(OsStr::to_str)
@@ -205,6 +661,15 @@ impl Type {
                 "md", "markdown", "mkdown", "mkdn", "mkd", "mdwn", "mdtxt", "mdtext", "text", "rmd",
             ],
             Self::Html => vec!["htm", "html", "xhtml"],
+            Self::Mdx => vec!["mdx"],
+            Self::Xml(XmlProfile::Svg) => vec!["svg"],
+            Self::Xml(XmlProfile::DocBook) => vec!["dbk", "docbook"],
+            Self::Xml(XmlProfile::Dita) => vec!["dita", "ditamap"],
+            Self::Xml(XmlProfile::Generic) => vec!["xml"],
+            Self::Plain => vec!["txt"],
+            // Only claimed for the configured languages, see `detect_by_name`
+            Self::Code => vec![],
+            Self::Gemtext => vec!["gmi", "gemini"],
         }
     }
 
@@ -213,6 +678,14 @@ impl Type {
         match self {
             Self::Markdown => "md",
             Self::Html => "html",
+            Self::Mdx => "mdx",
+            Self::Xml(XmlProfile::Svg) => "svg",
+            Self::Xml(XmlProfile::DocBook) => "docbook",
+            Self::Xml(XmlProfile::Dita) => "dita",
+            Self::Xml(XmlProfile::Generic) => "xml",
+            Self::Plain => "plain",
+            Self::Code => "code",
+            Self::Gemtext => "gmi",
         }
     }
 }
@@ -225,7 +698,156 @@ impl<'a> File<'a> {
             markup_type,
             locator: FileLoc::dummy(),
             start: Position::new(),
+            default_encoding: None,
+        }
+    }
+}
+
+impl File<'_> {
+    /// Creates a markup file from a path,
+    /// detecting its type as described in [`Type::detect`].
+    ///
+    /// The path may also be `-` for stdin,
+    /// which requires [`Config::markup_type`] to be set,
+    /// or an HTTP(S) URL, in which case the file is downloaded.
+    /// If [`Config::git_rev`] is set, local files are read
+    /// as they are stored in that revision.
+    ///
+    /// # Errors
+    ///
+    /// If the markup type could not be determined,
+    /// or an archive or compressed file is to be read from a git revision.
+    pub async fn from_path(path: PathBuf, conf: &Config) -> Result<Self, TypeExtractionError> {
+        let path_str = path.as_os_str().to_string_lossy();
+        if path_str == cli_utils::STREAM_PATH_STR {
+            let markup_type = conf
+                .markup_type
+                .ok_or(TypeExtractionError::StdinWithoutType)?;
+            return Ok(Self {
+                markup_type,
+                locator: Arc::new(FileLoc::from(path.as_path())),
+                content: Content::Stdin,
+                start: Position::new(),
+                default_encoding: conf.default_encoding(),
+            });
+        }
+        if let Ok(url) = Url::parse(&path_str)
+            && ["http", "https"].contains(&url.scheme())
+        {
+            let (markup_type, content) = Type::detect_url(&url, conf).await?;
+            return Ok(Self {
+                markup_type,
+                locator: Arc::new(FileLoc::Url(url)),
+                content,
+                start: Position::new(),
+                default_encoding: conf.default_encoding(),
+            });
+        }
+        if let Some(rev) = &conf.git_rev {
+            if archive::Format::from_file_name(&path_str).is_some() {
+                return Err(TypeExtractionError::ArchiveAtGitRev(path_str.into_owned()));
+            }
+            let markup_type = Type::detect_git_blob(rev, &path, conf).await?;
+            return Ok(Self {
+                markup_type,
+                locator: Arc::new(FileLoc::GitRev {
+                    rev: rev.clone(),
+                    path: FileSystemLoc::from(path.as_path()),
+                }),
+                content: Content::GitBlob {
+                    rev: rev.clone(),
+                    path,
+                },
+                start: Position::new(),
+                default_encoding: conf.default_encoding(),
+            });
+        }
+        let markup_type = Type::detect(path.as_path(), conf).await?;
+        let locator = Arc::new(FileLoc::from(path.as_path()));
+        let content = if archive::Format::from_file_name(&path_str) == Some(archive::Format::Gz) {
+            Content::Gzip(path)
+        } else {
+            Content::LocalFile(path)
+        };
+        Ok(Self {
+            markup_type,
+            locator,
+            content,
+            start: Position::new(),
+            default_encoding: conf.default_encoding(),
+        })
+    }
+
+    /// Creates all the markup files found at a path.
+    /// This is a single one, as described in [`Self::from_path`],
+    /// unless the path points to an archive (e.g. a zip file),
+    /// in which case it is one for every markup file within it.
+    /// Files within the archive of which we can not determine the markup type
+    /// are skipped.
+    ///
+    /// # Errors
+    ///
+    /// If the markup type could not be determined,
+    /// or the archive could not be read.
+    pub async fn all_from_path(
+        path: PathBuf,
+        conf: &Config,
+    ) -> Result<Vec<Self>, TypeExtractionError> {
+        let path_str = path.as_os_str().to_string_lossy().into_owned();
+        // NOTE With a git revision, archives are rejected by `from_path`,
+        //      instead of silently being read from the working tree.
+        let Some(format) = archive::Format::from_file_name(&path_str)
+            .filter(|candidate| candidate.is_archive() && conf.git_rev.is_none())
+        else {
+            return Self::from_path(path, conf).await.map(|file| vec![file]);
+        };
+        let archive_path: std::path::PathBuf = path.as_path().into();
+        let entries = task::spawn_blocking(move || archive::read_entries(&archive_path, format))
+            .await
+            .map_err(|err| TypeExtractionError::Archive(path_str.clone(), err))?;
+        let archive_loc = FileSystemLoc::from(path.as_path());
+        // An explicitly set markup type is meant for the archive file,
+        // not for each of the files within it.
+        let entry_conf = Config {
+            markup_type: None,
+            ..conf.clone()
+        };
+        let mut files = vec![];
+        for entry in entries {
+            let locator = FileLoc::Archive {
+                archive: archive_loc.clone(),
+                path: entry.path.as_str().into(),
+            };
+            let locator_str = locator.to_string();
+            let content = Content::Buffer(entry.content.into());
+            let detected = if conf.sniff_content {
+                let Ok(text) = content
+                    .fetch_with_default_encoding(conf.default_encoding())
+                    .await
+                else {
+                    log::trace!("Skipping non-text archive entry '{locator_str}'");
+                    continue;
+                };
+                Type::detect_content(&locator_str, &entry.path, &text, &entry_conf)
+            } else {
+                Type::detect_by_name(&locator_str, &entry.path, &entry_conf)
+            };
+            let markup_type = match detected {
+                Ok(markup_type) => markup_type,
+                Err(err) => {
+                    log::trace!("Skipping archive entry '{locator_str}': {err}");
+                    continue;
+                }
+            };
+            files.push(Self {
+                markup_type,
+                locator: Arc::new(locator),
+                content,
+                start: Position::new(),
+                default_encoding: conf.default_encoding(),
+            });
         }
+        Ok(files)
     }
 }
 
@@ -240,6 +862,7 @@ impl TryFrom<PathBuf> for File<'_> {
             locator,
             content: Content::LocalFile(path),
             start: Position::new(),
+            default_encoding: None,
         })
     }
 }
@@ -250,10 +873,136 @@ mod tests {
 
     #[test]
     fn all_lowercase_file_extensions() {
-        for mt in [Type::Markdown, Type::Html] {
+        for mt in Type::value_variants() {
             for ext in mt.file_extensions() {
                 assert_eq!(ext, ext.to_lowercase());
             }
         }
     }
+
+    #[test]
+    fn plain_file_names() {
+        assert_eq!(Type::try_from_file_name("LICENSE").ok(), Some(Type::Plain));
+        assert_eq!(
+            Type::try_from_file_name("vendor/lib/Copying").ok(),
+            Some(Type::Plain)
+        );
+        assert!(matches!(
+            Type::try_from_file_name("Makefile"),
+            Err(TypeExtractionError::NoFileExt)
+        ));
+    }
+
+    #[test]
+    fn code_only_for_configured_languages() {
+        let conf = Config::default();
+        assert!(matches!(
+            Type::detect_by_name("src/lib.rs", "src/lib.rs", &conf),
+            Err(TypeExtractionError::UnsupportedFileExt(_))
+        ));
+        let conf = Config {
+            code_languages: vec![Language::builtin("rust").expect("Builtin language")],
+            ..Config::default()
+        };
+        assert_eq!(
+            Type::detect_by_name("src/lib.rs", "src/lib.rs", &conf).ok(),
+            Some(Type::Code)
+        );
+        assert!(Type::detect_by_name("app.js", "app.js", &conf).is_err());
+        assert_eq!(
+            Type::detect_by_name("notes.txt", "notes.txt", &conf).ok(),
+            Some(Type::Plain)
+        );
+    }
+
+    #[test]
+    fn sniff() {
+        assert_eq!(
+            Type::sniff("<!DOCTYPE html>\n<html></html>"),
+            Some(Type::Html)
+        );
+        assert_eq!(Type::sniff("\n  <html lang=\"en\">"), Some(Type::Html));
+        assert_eq!(Type::sniff("# Project\n\nSome text"), Some(Type::Markdown));
+        assert_eq!(
+            Type::sniff("See [the docs](docs/index.md)."),
+            Some(Type::Markdown)
+        );
+        assert_eq!(
+            Type::sniff("[ref]: https://example.net/"),
+            Some(Type::Markdown)
+        );
+        assert_eq!(Type::sniff("# Project\n"), Some(Type::Markdown));
+        assert_eq!(Type::sniff("#!/bin/sh\necho hi"), None);
+        assert_eq!(
+            Type::sniff("#!/bin/sh\n# Install helper\n\n# See [docs](x.md)\n"),
+            None
+        );
+        assert_eq!(Type::sniff("# Install helper\nset -e\n"), None);
+        assert_eq!(Type::sniff("Just some [text]."), None);
+    }
+
+    #[test]
+    fn type_override() {
+        let type_override: TypeOverride = "*.mdx.txt=mdx".parse().expect("Valid override");
+        assert_eq!(type_override.markup_type, Type::Mdx);
+        assert!(type_override.matches("docs/intro.mdx.txt"));
+        assert!(!type_override.matches("docs/intro.txt"));
+        let readme: TypeOverride = "README=md".parse().expect("Valid override");
+        assert!(readme.matches("sub/dir/README"));
+        assert!("README".parse::<TypeOverride>().is_err());
+        assert!("README=unknown".parse::<TypeOverride>().is_err());
+        assert_eq!(readme.to_string(), "README=md");
+    }
+
+    const TYPE_DETECTION_DIR: &str = "./benches/type_detection";
+
+    async fn detect(file_name: &str, conf: &Config) -> Result<Type, TypeExtractionError> {
+        Type::detect(
+            Path::new(&format!("{TYPE_DETECTION_DIR}/{file_name}")),
+            conf,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn no_sniffing_by_default() {
+        let conf = Config::default();
+        assert!(matches!(
+            detect("README", &conf).await,
+            Err(TypeExtractionError::NoFileExt)
+        ));
+        assert_eq!(detect("intro.mdx.txt", &conf).await.ok(), Some(Type::Plain));
+    }
+
+    #[tokio::test]
+    async fn sniffing() {
+        let conf = Config {
+            sniff_content: true,
+            ..Config::default()
+        };
+        assert_eq!(detect("README", &conf).await.ok(), Some(Type::Markdown));
+        assert_eq!(detect("page", &conf).await.ok(), Some(Type::Html));
+        assert!(detect("install", &conf).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn overrides() {
+        let conf = Config {
+            type_overrides: vec![
+                "*.mdx.txt=mdx".parse().expect("Valid override"),
+                "*/page=md".parse().expect("Valid override"),
+            ],
+            sniff_content: true,
+            ..Config::default()
+        };
+        assert_eq!(detect("intro.mdx.txt", &conf).await.ok(), Some(Type::Mdx));
+        assert_eq!(detect("page", &conf).await.ok(), Some(Type::Markdown));
+    }
+
+    #[tokio::test]
+    async fn stdin_requires_markup_type() {
+        let conf = Config::default();
+        let res = File::from_path(PathBuf::from_str("-").expect("Infallible"), &conf).await;
+        assert!(matches!(res, Err(TypeExtractionError::StdinWithoutType)));
+    }
 }
@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Detection of scanned markup files that no other scanned file links to,
+//! and of those that can not be reached from any entry point.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use serde::Serialize;
+use wildmatch::WildMatch;
+
+use crate::graph::{Documents, document_id};
+use crate::link::{FileLoc, Link};
+
+/// The scanned markup files without incoming links.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize)]
+pub struct Report {
+    /// The scanned files matching one of the entry point globs
+    pub entry_points: Vec<String>,
+    /// The scanned files that no other scanned file links to,
+    /// not counting the entry points
+    pub orphans: Vec<String>,
+    /// The scanned files that can not be reached
+    /// by following links from any of the entry points.
+    /// This is empty if no entry point was scanned.
+    pub unreachable: Vec<String>,
+}
+
+/// Finds the scanned files that are not linked to by any other scanned file,
+/// and those not reachable from any of the files matching `entry_points`.
+///
+/// Link targets are resolved against the working directory,
+/// the same way they are in the graph and the backlinks index.
+#[must_use]
+pub fn find(scanned_files: &[Arc<FileLoc>], links: &[Link], entry_points: &[WildMatch]) -> Report {
+    let names: BTreeSet<String> = scanned_files.iter().map(|file| document_id(file)).collect();
+    let documents = Documents::new(scanned_files.iter().map(AsRef::as_ref));
+
+    let mut linked_to: HashSet<&str> = HashSet::new();
+    let mut edges: HashMap<String, BTreeSet<&str>> = HashMap::new();
+    for link in links {
+        let source = document_id(&link.source.file);
+        let Some(target) = documents
+            .target_id(link)
+            .and_then(|target| names.get(&target))
+        else {
+            continue;
+        };
+        if source != *target {
+            linked_to.insert(target.as_str());
+            edges.entry(source).or_default().insert(target.as_str());
+        }
+    }
+
+    let entries: Vec<&str> = names
+        .iter()
+        .filter(|name| entry_points.iter().any(|glob| glob.matches(name)))
+        .map(String::as_str)
+        .collect();
+
+    let mut reached: HashSet<&str> = entries.iter().copied().collect();
+    let mut queue: VecDeque<&str> = entries.iter().copied().collect();
+    while let Some(current) = queue.pop_front() {
+        for next in edges.get(current).into_iter().flatten() {
+            if reached.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let collect =
+        |filter: &dyn Fn(&str) -> bool| names.iter().filter(|name| filter(name)).cloned().collect();
+    Report {
+        entry_points: collect(&|name| entries.contains(&name)),
+        orphans: collect(&|name| !entries.contains(&name) && !linked_to.contains(name)),
+        unreachable: if entries.is_empty() {
+            log::warn!("None of the scanned files is an entry point; not checking reachability");
+            vec![]
+        } else {
+            collect(&|name| !reached.contains(name))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::Position;
+
+    fn file(path: &str) -> Arc<FileLoc> {
+        Arc::new(FileLoc::System(path.parse().expect("Valid path")))
+    }
+
+    fn link(source: &str, target: &str) -> Link {
+        Link::new(file(source), Position { line: 1, column: 1 }, target)
+    }
+
+    #[test]
+    fn orphans_and_unreachable() {
+        let scanned = [
+            file("README.md"),
+            file("docs/a.md"),
+            file("docs/b.md"),
+            file("docs/c.md"),
+            file("docs/d.md"),
+        ];
+        let links = [
+            link("README.md", "docs/a.md#intro"),
+            link("docs/a.md", "../README.md"),
+            link("docs/b.md", "./b.md#self"),
+            link("docs/c.md", "d.md"),
+            link("docs/d.md", "c.md"),
+        ];
+        let report = find(&scanned, &links, &[WildMatch::new("README.md")]);
+        assert_eq!(report.entry_points, vec!["README.md"]);
+        assert_eq!(report.orphans, vec!["docs/b.md"]);
+        assert_eq!(
+            report.unreachable,
+            vec!["docs/b.md", "docs/c.md", "docs/d.md"]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::backlinks::Entry;
+
+#[derive(Debug, Serialize)]
+struct SourceRec {
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct EntryRec<'a> {
+    trg_file: &'a str,
+    trg_anchor: Option<&'a str>,
+    sources: Vec<SourceRec>,
+}
+
+#[derive(Debug, Serialize)]
+struct FlatRec<'a> {
+    trg_file: &'a str,
+    trg_anchor: Option<&'a str>,
+    src_file: String,
+    src_line: usize,
+    src_column: usize,
+}
+
+impl super::Report for [Entry] {
+    const NAME: &'static str = "the backlinks index";
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        for entry in self {
+            let _ = writeln!(text, "{}", entry.target());
+            for source in &entry.sources {
+                let _ = writeln!(text, "  {source}");
+            }
+        }
+        text
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = "\n# Backlinks\n".to_owned();
+        for entry in self {
+            let _ = write!(
+                text,
+                "\n## `{}` ({} links)\n\n",
+                entry.target(),
+                entry.sources.len()
+            );
+            for source in &entry.sources {
+                let _ = writeln!(text, "- `{source}`");
+            }
+        }
+        text
+    }
+
+    fn json(&self) -> impl Serialize {
+        self.iter()
+            .map(|entry| EntryRec {
+                trg_file: &entry.file,
+                trg_anchor: entry.anchor.as_deref(),
+                sources: entry
+                    .sources
+                    .iter()
+                    .map(|source| SourceRec {
+                        file: source.file.to_string(),
+                        line: source.pos.line,
+                        column: source.pos.column,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// One record per linking source.
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send> {
+        Some(self.iter().flat_map(|entry| {
+            entry.sources.iter().map(|source| FlatRec {
+                trg_file: &entry.file,
+                trg_anchor: entry.anchor.as_deref(),
+                src_file: source.file.to_string(),
+                src_line: source.pos.line,
+                src_column: source.pos.column,
+            })
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{FileLoc, Locator, Position};
+    use crate::result::Report as _;
+    use std::sync::Arc;
+
+    #[test]
+    fn text() {
+        let entries = [Entry {
+            file: "docs/setup.md".to_owned(),
+            anchor: Some("install".to_owned()),
+            sources: vec![Locator {
+                file: Arc::new(FileLoc::System("README.md".parse().expect("Valid path"))),
+                pos: Position { line: 3, column: 5 },
+            }],
+        }];
+        assert_eq!(
+            entries.to_text(),
+            "docs/setup.md#install\n  README.md:3:5\n"
+        );
+    }
+}
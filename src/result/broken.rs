@@ -0,0 +1,85 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::validate::{BrokenLink, Reason};
+
+#[derive(Debug, Serialize)]
+struct Rec {
+    src_file: String,
+    src_line: usize,
+    src_column: usize,
+    target: String,
+    reason: Reason,
+}
+
+impl From<&BrokenLink> for Rec {
+    fn from(broken: &BrokenLink) -> Self {
+        Self {
+            src_file: broken.link.source.file.to_string(),
+            src_line: broken.link.source.pos.line,
+            src_column: broken.link.source.pos.column,
+            target: broken.link.target.to_string(),
+            reason: broken.reason,
+        }
+    }
+}
+
+impl super::Report for [BrokenLink] {
+    const NAME: &'static str = "the broken links";
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        for broken in self {
+            let _ = writeln!(text, "B:{broken}");
+        }
+        text
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = "\n# Broken Links\n\n".to_owned();
+        if self.is_empty() {
+            text.push_str("No broken links found.\n");
+            return text;
+        }
+        text.push_str("| Source | Target | Reason |\n| --- | --- | --- |\n");
+        for broken in self {
+            let _ = writeln!(
+                text,
+                "| `{}` | `{}` | {} |",
+                broken.link.source, broken.link.target, broken.reason
+            );
+        }
+        text
+    }
+
+    fn json(&self) -> impl Serialize {
+        self.iter().map(Rec::from).collect::<Vec<_>>()
+    }
+
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send> {
+        Some(self.iter().map(Rec::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{FileLoc, Link, Position};
+    use crate::result::Report as _;
+    use std::sync::Arc;
+
+    #[test]
+    fn text() {
+        let file = Arc::new(FileLoc::System("a.md".parse().expect("Valid path")));
+        let broken = BrokenLink {
+            link: Link::new(file, Position { line: 3, column: 5 }, "b.md#intro"),
+            reason: Reason::MissingAnchor,
+        };
+        assert_eq!([broken].to_text(), "B:missing-anchor:a.md:3:5:b.md#intro\n");
+    }
+}
@@ -8,12 +8,16 @@ use tokio::sync::Mutex;
 
 use crate::config::Tool as Config;
 use crate::result::Type;
+use crate::state::RemoteAnchors;
+use crate::url_mapping::UrlMapping;
 use crate::{anchor::Anchor, link::Link};
 
 use super::{AnchorRec, LinkRec, Writer, WriterOpt};
 
 pub struct Sink {
     extended: bool,
+    url_mappings: Vec<UrlMapping>,
+    remote_anchors: RemoteAnchors,
     flush: bool,
     links_writer: Option<Mutex<csv::AsyncSerializer<Writer>>>,
     anchors_writer: Option<Mutex<csv::AsyncSerializer<Writer>>>,
@@ -53,6 +57,8 @@ impl super::Sink for Sink {
     ) -> std::io::Result<Box<dyn super::Sink>> {
         Ok(Box::new(Self {
             extended: config.result_extended,
+            url_mappings: config.extractor.url_mappings.clone(),
+            remote_anchors: RemoteAnchors::default(),
             flush: config.result_flush,
             links_writer: Self::writer(format, links_stream),
             anchors_writer: Self::writer(format, anchors_stream),
@@ -63,7 +69,12 @@ impl super::Sink for Sink {
     async fn sink_link(&mut self, link: &Link) -> std::io::Result<()> {
         if let Some(links_writer_m) = &self.links_writer {
             let mut links_writer = links_writer_m.lock().await;
-            let rec = LinkRec::new(link, self.extended);
+            let rec = LinkRec::new(
+                link,
+                self.extended,
+                &self.url_mappings,
+                &self.remote_anchors,
+            );
             links_writer.serialize(rec).await?;
             if self.flush {
                 links_writer.flush().await?;
@@ -76,7 +87,7 @@ impl super::Sink for Sink {
     async fn sink_anchor(&mut self, anchor: &Anchor) -> std::io::Result<()> {
         if let Some(ref anchors_writer_m) = self.anchors_writer {
             let mut anchors_writer = anchors_writer_m.lock().await;
-            let rec = AnchorRec::new(anchor, self.extended);
+            let rec = AnchorRec::new(anchor, self.extended, &self.url_mappings);
             anchors_writer.serialize(rec).await?;
             if self.flush {
                 anchors_writer.flush().await?;
@@ -99,4 +110,8 @@ impl super::Sink for Sink {
 
         Ok(())
     }
+
+    fn set_remote_anchors(&mut self, remote_anchors: RemoteAnchors) {
+        self.remote_anchors = remote_anchors;
+    }
 }
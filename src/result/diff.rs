@@ -0,0 +1,281 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use async_std::io::{self, WriteExt};
+use async_std::path::Path;
+use serde::Serialize;
+
+use crate::diff::{Change, Entry, Location};
+use crate::graph::Graph;
+use crate::link::{FileLoc, FileSystemLoc, Link, Position};
+
+use super::{GraphType, Writer, graph};
+
+/// Whether a diff is about links or anchors.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Kind {
+    Links,
+    Anchors,
+}
+
+impl Kind {
+    /// The same prefix as used for plain text results
+    const fn prefix(self) -> char {
+        match self {
+            Self::Links => 'L',
+            Self::Anchors => 'A',
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Links => "Link",
+            Self::Anchors => "Anchor",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Rec<'a> {
+    change: Change,
+    value: &'a str,
+    old: Option<&'a Location>,
+    new: Option<&'a Location>,
+}
+
+#[derive(Debug, Serialize)]
+struct FlatRec<'a> {
+    change: Change,
+    value: &'a str,
+    old_file: Option<&'a str>,
+    old_line: Option<usize>,
+    old_column: Option<usize>,
+    new_file: Option<&'a str>,
+    new_line: Option<usize>,
+    new_column: Option<usize>,
+}
+
+impl<'a> From<&'a Entry> for FlatRec<'a> {
+    fn from(entry: &'a Entry) -> Self {
+        Self {
+            change: entry.change,
+            value: &entry.value,
+            old_file: entry.old.as_ref().map(|loc| loc.file.as_str()),
+            old_line: entry.old.as_ref().map(|loc| loc.line),
+            old_column: entry.old.as_ref().map(|loc| loc.column),
+            new_file: entry.new.as_ref().map(|loc| loc.file.as_str()),
+            new_line: entry.new.as_ref().map(|loc| loc.line),
+            new_column: entry.new.as_ref().map(|loc| loc.column),
+        }
+    }
+}
+
+fn loc_str(loc: Option<&Location>) -> String {
+    loc.map(ToString::to_string).unwrap_or_default()
+}
+
+/// The changes between two sets of either links or anchors.
+pub(super) struct Changes<'a> {
+    kind: Kind,
+    entries: &'a [Entry],
+}
+
+impl<'a> Changes<'a> {
+    pub(super) const fn new(kind: Kind, entries: &'a [Entry]) -> Self {
+        Self { kind, entries }
+    }
+}
+
+impl super::Report for Changes<'_> {
+    const NAME: &'static str = "changes";
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        let prefix = self.kind.prefix();
+        for entry in self.entries {
+            let _ = match entry.change {
+                Change::Added => writeln!(
+                    text,
+                    "+{prefix}:{}:{}",
+                    loc_str(entry.new.as_ref()),
+                    entry.value
+                ),
+                Change::Removed => writeln!(
+                    text,
+                    "-{prefix}:{}:{}",
+                    loc_str(entry.old.as_ref()),
+                    entry.value
+                ),
+                Change::Moved => writeln!(
+                    text,
+                    "~{prefix}:{}:{} (from {})",
+                    loc_str(entry.new.as_ref()),
+                    entry.value,
+                    loc_str(entry.old.as_ref())
+                ),
+            };
+        }
+        text
+    }
+
+    fn to_markdown(&self) -> String {
+        let name = self.kind.name();
+        let mut text = format!("\n# {name} Changes\n\n");
+        if self.entries.is_empty() {
+            text.push_str("No changes.\n");
+            return text;
+        }
+        let _ = writeln!(
+            text,
+            "| Change | {name} | Old | New |\n| --- | --- | --- | --- |"
+        );
+        let code =
+            |loc: Option<&Location>| loc.map(|known| format!("`{known}`")).unwrap_or_default();
+        for entry in self.entries {
+            let _ = writeln!(
+                text,
+                "| {} | `{}` | {} | {} |",
+                entry.change,
+                entry.value,
+                code(entry.old.as_ref()),
+                code(entry.new.as_ref())
+            );
+        }
+        text
+    }
+
+    fn json(&self) -> impl Serialize {
+        self.entries
+            .iter()
+            .map(|entry| Rec {
+                change: entry.change,
+                value: &entry.value,
+                old: entry.old.as_ref(),
+                new: entry.new.as_ref(),
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send> {
+        Some(self.entries.iter().map(FlatRec::from))
+    }
+}
+
+/// The document-level graphs of the added, removed and moved links.
+/// Removed links are placed where they were,
+/// the others where they are now.
+fn graphs(entries: &[Entry]) -> Vec<(Change, Graph)> {
+    [Change::Added, Change::Removed, Change::Moved]
+        .into_iter()
+        .map(|change| {
+            let links: Vec<Link> = entries
+                .iter()
+                .filter(|entry| entry.change == change)
+                .filter_map(|entry| {
+                    let loc = entry.location()?;
+                    let file = FileLoc::System(FileSystemLoc::from(Path::new(&loc.file)));
+                    Some(Link::new(
+                        Arc::new(file),
+                        Position {
+                            line: loc.line,
+                            column: loc.column,
+                        },
+                        &entry.value,
+                    ))
+                })
+                .collect();
+            (change, Graph::from_links(&links))
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct JsonGraphs {
+    added: Graph,
+    removed: Graph,
+    moved: Graph,
+}
+
+fn to_json_graphs(entries: &[Entry]) -> serde_json::Result<String> {
+    let mut by_change = graphs(entries).into_iter().map(|(_, graph)| graph);
+    let recs = JsonGraphs {
+        added: by_change.next().unwrap_or_default(),
+        removed: by_change.next().unwrap_or_default(),
+        moved: by_change.next().unwrap_or_default(),
+    };
+    serde_json::to_string_pretty(&recs)
+}
+
+/// Writes the document-level graphs of the added, removed and moved links
+/// in the given format.
+///
+/// # Errors
+///
+/// If writing to the stream failed.
+pub async fn write_graph(
+    format: GraphType,
+    mut stream: Writer,
+    entries: &[Entry],
+) -> io::Result<()> {
+    let content = match format {
+        GraphType::Dot => graph::diff_to_dot(&graphs(entries)),
+        GraphType::GraphMl => graph::diff_to_graphml(&graphs(entries)),
+        GraphType::Json => to_json_graphs(entries)?,
+    };
+    stream.write_all(content.as_bytes()).await?;
+    stream.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::Report as _;
+
+    fn loc(file: &str, line: usize) -> Location {
+        Location {
+            file: file.to_owned(),
+            line,
+            column: 1,
+        }
+    }
+
+    fn entries() -> Vec<Entry> {
+        vec![
+            Entry {
+                change: Change::Removed,
+                value: "gone.md".to_owned(),
+                old: Some(loc("README.md", 2)),
+                new: None,
+            },
+            Entry {
+                change: Change::Moved,
+                value: "https://example.org".to_owned(),
+                old: Some(loc("docs/a.md", 3)),
+                new: Some(loc("docs/b.md", 1)),
+            },
+        ]
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            Changes::new(Kind::Links, &entries()).to_text(),
+            "-L:README.md:2:1:gone.md
+~L:docs/b.md:1:1:https://example.org (from docs/a.md:3:1)
+"
+        );
+    }
+
+    #[test]
+    fn dot() {
+        let dot = graph::diff_to_dot(&graphs(&entries()));
+        assert!(dot.contains(
+            r#""README.md" -> "gone.md" [label="1", count=1, fragments="", change="removed", color=red];"#
+        ));
+        assert!(dot.contains(r#""docs/b.md" -> "example.org""#));
+    }
+}
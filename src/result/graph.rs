@@ -0,0 +1,225 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use async_std::io::{self, WriteExt};
+
+use crate::diff::Change;
+use crate::graph::{Edge, Graph, Node, NodeKind};
+
+use super::{GraphType, Writer};
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn fragments_str(edge: &Edge) -> String {
+    edge.fragments
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The edges of a graph,
+/// each with what happened to it, if the graph is a diff.
+type TaggedEdges<'a> = Vec<(Option<Change>, &'a Edge)>;
+
+fn untagged(graph: &Graph) -> TaggedEdges<'_> {
+    graph.edges.iter().map(|edge| (None, edge)).collect()
+}
+
+/// The nodes of all the `graphs`, sorted by id,
+/// and their edges, tagged with the change of their graph.
+fn merge(graphs: &[(Change, Graph)]) -> (Vec<Node>, TaggedEdges<'_>) {
+    let mut kinds: BTreeMap<&str, NodeKind> = BTreeMap::new();
+    let mut edges = vec![];
+    for (change, graph) in graphs {
+        for node in &graph.nodes {
+            kinds.entry(&node.id).or_insert(node.kind);
+        }
+        edges.extend(graph.edges.iter().map(|edge| (Some(*change), edge)));
+    }
+    let nodes = kinds
+        .into_iter()
+        .map(|(id, kind)| Node {
+            id: id.to_owned(),
+            kind,
+        })
+        .collect();
+    (nodes, edges)
+}
+
+const fn change_color(change: Change) -> &'static str {
+    match change {
+        Change::Added => "green",
+        Change::Removed => "red",
+        Change::Moved => "blue",
+    }
+}
+
+fn dot(nodes: &[Node], edges: &TaggedEdges) -> String {
+    let mut text = "digraph links {\n".to_owned();
+    for node in nodes {
+        let _ = writeln!(
+            text,
+            "  \"{}\" [kind=\"{}\", shape={}];",
+            dot_escape(&node.id),
+            node.kind,
+            if node.kind == NodeKind::Host {
+                "ellipse"
+            } else {
+                "box"
+            }
+        );
+    }
+    for (edge_change, edge) in edges {
+        let change_attrs = edge_change.map_or_else(String::new, |change| {
+            format!(", change=\"{change}\", color={}", change_color(change))
+        });
+        let _ = writeln!(
+            text,
+            "  \"{}\" -> \"{}\" [label=\"{}\", count={}, fragments=\"{}\"{change_attrs}];",
+            dot_escape(&edge.source),
+            dot_escape(&edge.target),
+            edge.count,
+            edge.count,
+            dot_escape(&fragments_str(edge))
+        );
+    }
+    text.push_str("}\n");
+    text
+}
+
+fn to_dot(graph: &Graph) -> String {
+    dot(&graph.nodes, &untagged(graph))
+}
+
+/// The graphs of the added, removed and moved links as one DOT graph,
+/// with each edge colored by what happened to it.
+pub(super) fn diff_to_dot(graphs: &[(Change, Graph)]) -> String {
+    let (nodes, edges) = merge(graphs);
+    dot(&nodes, &edges)
+}
+
+fn graphml(nodes: &[Node], edges: &TaggedEdges) -> String {
+    let mut text = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="kind" for="node" attr.name="kind" attr.type="string"/>
+  <key id="count" for="edge" attr.name="count" attr.type="int"/>
+  <key id="fragments" for="edge" attr.name="fragments" attr.type="string"/>
+"#
+    .to_owned();
+    if edges.iter().any(|(change, _)| change.is_some()) {
+        text.push_str(
+            "  <key id=\"change\" for=\"edge\" attr.name=\"change\" attr.type=\"string\"/>\n",
+        );
+    }
+    text.push_str("  <graph id=\"links\" edgedefault=\"directed\">\n");
+    for node in nodes {
+        let _ = writeln!(
+            text,
+            "    <node id=\"{}\"><data key=\"kind\">{}</data></node>",
+            xml_escape(&node.id),
+            node.kind
+        );
+    }
+    for (edge_change, edge) in edges {
+        let change_data = edge_change.map_or_else(String::new, |change| {
+            format!("<data key=\"change\">{change}</data>")
+        });
+        let _ = writeln!(
+            text,
+            "    <edge source=\"{}\" target=\"{}\"><data key=\"count\">{}</data><data key=\"fragments\">{}</data>{change_data}</edge>",
+            xml_escape(&edge.source),
+            xml_escape(&edge.target),
+            edge.count,
+            xml_escape(&fragments_str(edge))
+        );
+    }
+    text.push_str("  </graph>\n</graphml>\n");
+    text
+}
+
+fn to_graphml(graph: &Graph) -> String {
+    graphml(&graph.nodes, &untagged(graph))
+}
+
+/// The graphs of the added, removed and moved links as one `GraphML` graph,
+/// with each edge annotated with what happened to it.
+pub(super) fn diff_to_graphml(graphs: &[(Change, Graph)]) -> String {
+    let (nodes, edges) = merge(graphs);
+    graphml(&nodes, &edges)
+}
+
+/// Writes the document-level graph of links in the given format.
+///
+/// # Errors
+///
+/// If writing to the stream failed.
+pub async fn write(format: GraphType, mut stream: Writer, graph: &Graph) -> io::Result<()> {
+    let content = match format {
+        GraphType::Dot => to_dot(graph),
+        GraphType::GraphMl => to_graphml(graph),
+        GraphType::Json => serde_json::to_string_pretty(graph)?,
+    };
+    stream.write_all(content.as_bytes()).await?;
+    stream.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        Graph {
+            nodes: vec![
+                Node {
+                    id: "README.md".to_owned(),
+                    kind: NodeKind::Document,
+                },
+                Node {
+                    id: "example.org".to_owned(),
+                    kind: NodeKind::Host,
+                },
+            ],
+            edges: vec![Edge {
+                source: "README.md".to_owned(),
+                target: "example.org".to_owned(),
+                count: 2,
+                fragments: ["a".to_owned(), "b&c".to_owned()].into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn dot() {
+        assert_eq!(
+            to_dot(&graph()),
+            r#"digraph links {
+  "README.md" [kind="document", shape=box];
+  "example.org" [kind="host", shape=ellipse];
+  "README.md" -> "example.org" [label="2", count=2, fragments="a b&c"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn graphml() {
+        assert!(to_graphml(&graph()).contains(
+            r#"<edge source="README.md" target="example.org"><data key="count">2</data><data key="fragments">a b&amp;c</data></edge>"#
+        ));
+    }
+}
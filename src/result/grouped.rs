@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::group::Group;
+
+#[derive(Debug, Serialize)]
+struct SourceRec {
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct GroupRec<'a> {
+    target: &'a str,
+    count: usize,
+    fragments: Vec<&'a str>,
+    sources: Vec<SourceRec>,
+}
+
+/// One line per group, with the lists joined by spaces.
+#[derive(Debug, Serialize)]
+struct FlatRec<'a> {
+    target: &'a str,
+    count: usize,
+    fragments: String,
+    sources: String,
+}
+
+fn join<T: ToString>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl super::Report for [Group] {
+    const NAME: &'static str = "links grouped by target";
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        for group in self {
+            let _ = writeln!(text, "G:{} ({})", group.target, group.sources.len());
+            for fragment in &group.fragments {
+                let _ = writeln!(text, "  #{fragment}");
+            }
+            for source in &group.sources {
+                let _ = writeln!(text, "  {source}");
+            }
+        }
+        text
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = "\n# Links Grouped by Target\n\n| Target | Count | Fragments | Sources |\n| --- | --- | --- | --- |\n".to_owned();
+        for group in self {
+            let _ = writeln!(
+                text,
+                "| `{}` | {} | {} | {} |",
+                group.target,
+                group.sources.len(),
+                join(
+                    group
+                        .fragments
+                        .iter()
+                        .map(|fragment| format!("`#{fragment}`"))
+                ),
+                join(group.sources.iter().map(|source| format!("`{source}`"))),
+            );
+        }
+        text
+    }
+
+    fn json(&self) -> impl Serialize {
+        self.iter()
+            .map(|group| GroupRec {
+                target: &group.target,
+                count: group.sources.len(),
+                fragments: group.fragments.iter().map(String::as_str).collect(),
+                sources: group
+                    .sources
+                    .iter()
+                    .map(|source| SourceRec {
+                        file: source.file.to_string(),
+                        line: source.pos.line,
+                        column: source.pos.column,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send> {
+        Some(self.iter().map(|group| FlatRec {
+            target: &group.target,
+            count: group.sources.len(),
+            fragments: join(&group.fragments),
+            sources: join(&group.sources),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{FileLoc, Locator, Position};
+    use crate::result::Report as _;
+    use std::sync::Arc;
+
+    #[test]
+    fn text() {
+        let source = |line| Locator {
+            file: Arc::new(FileLoc::System("README.md".parse().expect("Valid path"))),
+            pos: Position { line, column: 1 },
+        };
+        let groups = [Group {
+            target: "https://example.org/guide".to_owned(),
+            fragments: ["install".to_owned()].into(),
+            sources: vec![source(1), source(4)],
+        }];
+        assert_eq!(
+            groups.to_text(),
+            "G:https://example.org/guide (2)\n  #install\n  README.md:1:1\n  README.md:4:1\n"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::ignored::{Kind, Report};
+
+#[derive(Debug, Serialize)]
+struct Rec<'a> {
+    kind: Kind,
+    glob: &'a str,
+    item: Option<&'a str>,
+}
+
+impl super::Report for Report {
+    const NAME: &'static str = "the report of ignored items";
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        for rule in &self.rules {
+            let _ = writeln!(
+                text,
+                "{} '{}': {} ignored",
+                rule.kind,
+                rule.glob,
+                rule.items.len()
+            );
+            for item in &rule.items {
+                let _ = writeln!(text, "  {item}");
+            }
+        }
+        text
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = "\n# Report of Ignored Items\n".to_owned();
+        for rule in &self.rules {
+            let _ = write!(
+                text,
+                "\n## {} `{}` ({} ignored)\n\n",
+                rule.kind,
+                rule.glob,
+                rule.items.len()
+            );
+            if rule.items.is_empty() {
+                text.push_str("This rule never matched anything.\n");
+            }
+            for item in &rule.items {
+                let _ = writeln!(text, "- `{item}`");
+            }
+        }
+        text
+    }
+
+    fn json(&self) -> impl Serialize {
+        self
+    }
+
+    /// One record per ignored item,
+    /// and one without an item for each rule that never matched.
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send> {
+        Some(self.rules.iter().flat_map(|rule| {
+            rule.items
+                .iter()
+                .map(|item| Some(item.as_str()))
+                .chain(rule.items.is_empty().then_some(None))
+                .map(|item| Rec {
+                    kind: rule.kind,
+                    glob: &rule.glob,
+                    item,
+                })
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ignored::Rule;
+    use crate::result::Report as _;
+
+    #[test]
+    fn text() {
+        let report = Report {
+            rules: vec![
+                Rule {
+                    kind: Kind::Link,
+                    glob: "https://*".to_owned(),
+                    items: vec!["a.md:1:1:https://example.com".to_owned()],
+                },
+                Rule {
+                    kind: Kind::Anchor,
+                    glob: "toc-*".to_owned(),
+                    items: vec![],
+                },
+            ],
+        };
+        assert_eq!(
+            report.to_text(),
+            "link 'https://*': 1 ignored\n  a.md:1:1:https://example.com\nanchor 'toc-*': 0 ignored\n"
+        );
+    }
+}
@@ -8,12 +8,16 @@ use tokio::sync::Mutex;
 
 use crate::config::Tool as Config;
 use crate::link::Link;
+use crate::state::RemoteAnchors;
+use crate::url_mapping::UrlMapping;
 use crate::{anchor::Anchor, result::Type};
 
 use super::{AnchorOwnedRec, LinkOwnedRec, Writer, WriterOpt};
 
 pub struct Sink {
     extended: bool,
+    url_mappings: Vec<UrlMapping>,
+    remote_anchors: RemoteAnchors,
     links_stream: Option<Mutex<Writer>>,
     anchors_stream: Option<Mutex<Writer>>,
     links: Vec<LinkOwnedRec>,
@@ -30,6 +34,8 @@ impl super::Sink for Sink {
     ) -> std::io::Result<Box<dyn super::Sink>> {
         Ok(Box::new(Self {
             extended: config.result_extended,
+            url_mappings: config.extractor.url_mappings.clone(),
+            remote_anchors: RemoteAnchors::default(),
             links_stream: links_stream.map(Mutex::new),
             anchors_stream: anchors_stream.map(Mutex::new),
             links: vec![],
@@ -38,13 +44,21 @@ impl super::Sink for Sink {
     }
 
     async fn sink_link(&mut self, link: &Link) -> std::io::Result<()> {
-        self.links.push(LinkOwnedRec::new(link, self.extended));
+        self.links.push(LinkOwnedRec::new(
+            link,
+            self.extended,
+            &self.url_mappings,
+            &self.remote_anchors,
+        ));
         Ok(())
     }
 
     async fn sink_anchor(&mut self, anchor: &Anchor) -> std::io::Result<()> {
-        self.anchors
-            .push(AnchorOwnedRec::new(anchor, self.extended));
+        self.anchors.push(AnchorOwnedRec::new(
+            anchor,
+            self.extended,
+            &self.url_mappings,
+        ));
         Ok(())
     }
 
@@ -64,4 +78,8 @@ impl super::Sink for Sink {
 
         Ok(())
     }
+
+    fn set_remote_anchors(&mut self, remote_anchors: RemoteAnchors) {
+        self.remote_anchors = remote_anchors;
+    }
 }
@@ -11,12 +11,21 @@ use tokio::sync::Mutex;
 use crate::anchor::Anchor;
 use crate::config::Tool as Config;
 use crate::link::Link;
+use crate::state::RemoteAnchors;
+use crate::url_mapping::{self, UrlMapping};
 
 use super::{Type, Writer, WriterOpt};
 
+/// Formats an optional value as inline code, or as nothing.
+fn code_opt<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(String::new, |val| format!("`{val}`"))
+}
+
 #[allow(clippy::struct_excessive_bools)]
 pub struct Sink {
     extended: bool,
+    url_mappings: Vec<UrlMapping>,
+    remote_anchors: RemoteAnchors,
     markup_files: Vec<PathBuf>,
     flush: bool,
     stream: Mutex<Writer>,
@@ -86,12 +95,15 @@ impl Sink {
 | Source-is-URL \
 | Source-is-Local \
 | Source-is-Remote \
+| Source-Counterpart \
 | Target \
 | Target-Fragment \
 | Target-is-File \
 | Target-is-URL \
 | Target-is-Local \
 | Target-is-Remote \
+| Target-Counterpart \
+| Target-Remote-Fragment \
 |"
             )
             .await?;
@@ -110,6 +122,9 @@ impl Sink {
 | --- \
 | --- \
 | --- \
+| --- \
+| --- \
+| --- \
 |"
             )
             .await?;
@@ -163,6 +178,7 @@ impl Sink {
 | is-URL \
 | is-Local \
 | is-Remote \
+| Counterpart \
 | Name \
 | Type \
 |"
@@ -179,6 +195,7 @@ impl Sink {
 | --- \
 | --- \
 | --- \
+| --- \
 |"
             )
             .await?;
@@ -225,6 +242,8 @@ because the chosen output format writes everything into one file."
             .expect("Either links or anchors (or both) to have an output target");
         Ok(Box::new(Self {
             extended: config.result_extended,
+            url_mappings: config.extractor.url_mappings.clone(),
+            remote_anchors: RemoteAnchors::default(),
             markup_files: if config.result_extended {
                 config.extractor.markup_files.clone()
             } else {
@@ -254,7 +273,7 @@ because the chosen output format writes everything into one file."
         });
         let line = if self.extended {
             format!(
-                "| [`{}`]({}) | {} | {} | {} | {} | {} | {} | {}| {}| {} | {} | {} | {} |",
+                "| [`{}`]({}) | {} | {} | {} | {} | {} | {} | {} | {}| {}| {} | {} | {} | {} | {} | {} |",
                 link.source.file,
                 link.source.file,
                 link.source.pos.line,
@@ -263,12 +282,22 @@ because the chosen output format writes everything into one file."
                 link.source.file.is_url(),
                 link.source.file.is_local(),
                 link.source.file.is_remote(),
+                code_opt(url_mapping::file_counterpart(
+                    &self.url_mappings,
+                    &link.source.file
+                )),
                 target_no_frag,
                 target_frag,
                 link.target.is_file_system(),
                 link.target.is_url(),
                 link.target.is_local(),
                 link.target.is_remote(),
+                code_opt(url_mapping::target_counterpart(
+                    &self.url_mappings,
+                    &link.source.file,
+                    &link.target
+                )),
+                code_opt(self.remote_anchors.fragment(&link.target)),
             )
         } else {
             format!(
@@ -294,7 +323,7 @@ because the chosen output format writes everything into one file."
         let mut writer = self.stream.lock().await;
         let line = if self.extended {
             format!(
-                "| [`{}`]({}) | {} | {} | {} | {} | {} | {} | {} | {} |",
+                "| [`{}`]({}) | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
                 anchor.source.file,
                 anchor.source.file,
                 anchor.source.pos.line,
@@ -303,6 +332,10 @@ because the chosen output format writes everything into one file."
                 anchor.source.file.is_url(),
                 anchor.source.file.is_local(),
                 anchor.source.file.is_remote(),
+                code_opt(url_mapping::file_counterpart(
+                    &self.url_mappings,
+                    &anchor.source.file
+                )),
                 anchor.name,
                 anchor.r#type,
             )
@@ -326,4 +359,8 @@ because the chosen output format writes everything into one file."
     async fn finalize(&mut self) -> io::Result<()> {
         Ok(())
     }
+
+    fn set_remote_anchors(&mut self, remote_anchors: RemoteAnchors) {
+        self.remote_anchors = remote_anchors;
+    }
 }
@@ -2,12 +2,20 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+mod backlinks;
+mod broken;
 mod csv;
+mod diff;
+mod graph;
+mod grouped;
+mod ignored;
 mod json;
 mod markdown;
+mod orphans;
+mod summary;
 mod txt;
 
-use async_std::io::{ErrorKind, Write};
+use async_std::io::{ErrorKind, Write, WriteExt};
 use async_trait::async_trait;
 use cli_utils::StreamIdent;
 use std::str::FromStr;
@@ -19,13 +27,16 @@ use std::str::FromStr;
 
 use async_std::io;
 use clap::{ValueEnum, builder::PossibleValue};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
 
 use crate::{
     BoxError,
     anchor::{self, Anchor},
     config::Tool as Config,
+    group,
     link::Link,
+    state::{RemoteAnchors, RemoteCache, RemoteFragment},
+    url_mapping::{self, UrlMapping},
 };
 
 type Writer = Box<dyn Write + Unpin + Send + Sync + 'static>;
@@ -45,8 +56,15 @@ const ALL_EXTS: [&str; 6] = [
     EXT_JSON,
     EXT_RDF_TURTLE,
 ];
+const EXT_DOT: &str = "dot";
+const EXT_GRAPHML: &str = "graphml";
+const ALL_GRAPH_EXTS: [&str; 3] = [EXT_DOT, EXT_GRAPHML, EXT_JSON];
 
-#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize)]
+/// The format of the results.
+///
+/// It (de-)serializes as [`Self::as_str`],
+/// the same as it is given on the command line.
+#[derive(Default, Debug, Clone, Copy)]
 pub enum Type {
     #[default]
     Text,
@@ -96,6 +114,7 @@ impl FromStr for Type {
             EXT_TEXT | "text" | "plain" | "grep" => Self::Text,
             EXT_MARKDOWN | "markdown" => Self::Markdown,
             EXT_CSV => Self::Csv,
+            EXT_TSV => Self::Tsv,
             EXT_JSON => Self::Json,
             EXT_RDF_TURTLE | "turtle" | "rdf" | "rdf-turtle" => Self::RdfTurtle,
             _ => Err(std::io::Error::new(
@@ -110,6 +129,91 @@ impl FromStr for Type {
     }
 }
 
+impl Serialize for Type {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Type {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// The format of the document-level link graph;
+/// see [`crate::graph::Graph`].
+///
+/// It (de-)serializes as [`Self::as_str`],
+/// the same as it is given on the command line.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphType {
+    /// `GraphViz` DOT
+    #[default]
+    Dot,
+    /// `GraphML`
+    GraphMl,
+    /// JSON nodes and edges
+    Json,
+}
+
+impl ValueEnum for GraphType {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Dot, Self::GraphMl, Self::Json]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(self.as_str().into())
+    }
+}
+
+impl GraphType {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dot => EXT_DOT,
+            Self::GraphMl => EXT_GRAPHML,
+            Self::Json => EXT_JSON,
+        }
+    }
+}
+
+impl FromStr for GraphType {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            EXT_DOT | "gv" | "graphviz" => Self::Dot,
+            EXT_GRAPHML => Self::GraphMl,
+            EXT_JSON => Self::Json,
+            _ => Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Invalid graph format given: '{}' \nValid formats are: {}",
+                    s,
+                    ALL_GRAPH_EXTS.join(", ")
+                ),
+            ))?,
+        })
+    }
+}
+
+impl Serialize for GraphType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for GraphType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
 #[allow(clippy::ref_option)]
 async fn construct_out_stream_opt(
     specifier_opt: &Option<StreamIdent>,
@@ -127,8 +231,80 @@ pub fn write_to_stderr(errors: &[BoxError]) {
     }
 }
 
+/// A report that is written as a whole,
+/// in one of the result formats.
+trait Report: Sync {
+    /// What the report is called in error messages
+    const NAME: &'static str;
+
+    fn to_text(&self) -> String;
+
+    fn to_markdown(&self) -> String;
+
+    /// What gets written in the JSON format
+    fn json(&self) -> impl Serialize;
+
+    /// The records written in the CSV and TSV formats,
+    /// or `None` if the report does not support these.
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send>;
+}
+
+fn unsupported(format: Type, name: &str) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+            "Result format '{}' not supported for {name}",
+            format.as_str()
+        ),
+    )
+}
+
+async fn write_report<R: Report + ?Sized>(
+    format: Type,
+    mut stream: Writer,
+    report: &R,
+) -> io::Result<()> {
+    let content = match format {
+        Type::Text => report.to_text(),
+        Type::Markdown => report.to_markdown(),
+        Type::Json => serde_json::to_string_pretty(&report.json())?,
+        Type::Csv | Type::Tsv => {
+            let recs = report.recs().ok_or_else(|| unsupported(format, R::NAME))?;
+            let mut writer = ::csv_async::AsyncWriterBuilder::new()
+                .delimiter(if matches!(format, Type::Tsv) {
+                    b'\t'
+                } else {
+                    b';'
+                })
+                .create_serializer(stream);
+            for rec in recs {
+                writer.serialize(rec).await?;
+            }
+            return writer.flush().await;
+        }
+        Type::RdfTurtle => return Err(unsupported(format, R::NAME)),
+    };
+    stream.write_all(content.as_bytes()).await?;
+    stream.flush().await
+}
+
+#[allow(clippy::ref_option)]
+async fn sink_report<R: Report + ?Sized>(
+    format: Type,
+    specifier_opt: &Option<StreamIdent>,
+    report: &R,
+) -> io::Result<()> {
+    match construct_out_stream_opt(specifier_opt).await? {
+        Some(writer) => write_report(format, writer, report).await,
+        None => Ok(()),
+    }
+}
+
 /// Write results to stdout or file.
 ///
+/// With [`Config::result_grouped`], links are written grouped by target,
+/// see [`group::by_target`].
+///
 /// # Errors
 ///
 /// (I/)O-error when writing to a file.
@@ -137,6 +313,40 @@ pub async fn sink(
     links: &[Link],
     anchors: &[Anchor],
     errors: &[BoxError],
+) -> io::Result<()> {
+    sink_annotated(config, links, anchors, errors, RemoteAnchors::default()).await
+}
+
+/// Like [`sink`], but in the extended formats,
+/// fragments of links to remote pages are annotated
+/// with whether they match the anchors in `remote_cache`.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file.
+pub async fn sink_with_remote_cache(
+    config: &Config,
+    links: &[Link],
+    anchors: &[Anchor],
+    errors: &[BoxError],
+    remote_cache: &RemoteCache,
+) -> io::Result<()> {
+    sink_annotated(
+        config,
+        links,
+        anchors,
+        errors,
+        RemoteAnchors::from(remote_cache),
+    )
+    .await
+}
+
+async fn sink_annotated(
+    config: &Config,
+    links: &[Link],
+    anchors: &[Anchor],
+    errors: &[BoxError],
+    remote_anchors: RemoteAnchors,
 ) -> io::Result<()> {
     let sink_init = match config.result_format {
         Type::Text => txt::Sink::init,
@@ -148,10 +358,31 @@ pub async fn sink(
             "Result format not yet supported",
         ))?,
     };
-    let links_writer = construct_out_stream_opt(&config.links).await?;
+    if config.links.is_none() && config.anchors.is_none() {
+        // Only other reports were requested
+        write_to_stderr(errors);
+        return Ok(());
+    }
+    let mut links_writer = construct_out_stream_opt(&config.links).await?;
     let anchors_writer = construct_out_stream_opt(&config.anchors).await?;
+    if config.result_grouped
+        && let Some(writer) = links_writer.take()
+    {
+        write_report(
+            config.result_format,
+            writer,
+            group::by_target(links).as_slice(),
+        )
+        .await?;
+        if anchors_writer.is_none() {
+            write_to_stderr(errors);
+            return Ok(());
+        }
+    }
     let mut sink = sink_init(config.result_format, config, links_writer, anchors_writer).await?;
-    for link in links {
+    sink.set_remote_anchors(remote_anchors);
+    let sunk_links = if config.result_grouped { &[] } else { links };
+    for link in sunk_links {
         // thread::sleep::sleep(std::time::Duration::new(0, 200000000));
         sink.sink_link(link).await?;
     }
@@ -164,6 +395,119 @@ pub async fn sink(
     sink.finalize().await
 }
 
+/// Writes the report of what each ignore rule has ignored
+/// to [`Config::ignored`], if set.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file,
+/// or if the result format is not supported.
+pub async fn sink_ignored(config: &Config, report: &crate::ignored::Report) -> io::Result<()> {
+    sink_report(config.result_format, &config.ignored, report).await
+}
+
+/// Writes the broken links found by the offline validation
+/// to [`Config::validation`], if set.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file,
+/// or if the result format is not supported.
+pub async fn sink_broken(
+    config: &Config,
+    broken_links: &[crate::validate::BrokenLink],
+) -> io::Result<()> {
+    sink_report(config.result_format, &config.validation, broken_links).await
+}
+
+/// Writes the report of orphan and unreachable pages
+/// to [`Config::orphans`], if set.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file,
+/// or if the result format is not supported.
+pub async fn sink_orphans(config: &Config, report: &crate::orphans::Report) -> io::Result<()> {
+    sink_report(config.result_format, &config.orphans, report).await
+}
+
+/// Writes the backlinks index
+/// to [`Config::backlinks`], if set.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file,
+/// or if the result format is not supported.
+pub async fn sink_backlinks(
+    config: &Config,
+    entries: &[crate::backlinks::Entry],
+) -> io::Result<()> {
+    sink_report(config.result_format, &config.backlinks, entries).await
+}
+
+/// Writes the summary statistics
+/// to [`Config::summary`], if set.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file,
+/// or if the result format is not supported.
+pub async fn sink_summary(config: &Config, summary: &crate::summary::Summary) -> io::Result<()> {
+    sink_report(config.result_format, &config.summary, summary).await
+}
+
+/// Writes the document-level graph of `links`
+/// to [`Config::graph`], if set.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file.
+pub async fn sink_graph(config: &Config, links: &[Link]) -> io::Result<()> {
+    let Some(writer) = construct_out_stream_opt(&config.graph).await? else {
+        return Ok(());
+    };
+    graph::write(
+        config.graph_format,
+        writer,
+        &crate::graph::Graph::from_links(links),
+    )
+    .await
+}
+
+/// Writes the changes between two sets of links
+/// to [`Config::links`], if set,
+/// and those between two sets of anchors
+/// to [`Config::anchors`], if set.
+///
+/// The graphs of the changed links are written
+/// to [`Config::graph`], if set.
+///
+/// # Errors
+///
+/// (I/)O-error when writing to a file,
+/// or if the result format is not supported.
+pub async fn sink_diff(
+    config: &Config,
+    links: &[crate::diff::Entry],
+    anchors: &[crate::diff::Entry],
+) -> io::Result<()> {
+    sink_report(
+        config.result_format,
+        &config.links,
+        &diff::Changes::new(diff::Kind::Links, links),
+    )
+    .await?;
+    if let Some(writer) = construct_out_stream_opt(&config.graph).await? {
+        diff::write_graph(config.graph_format, writer, links).await?;
+    }
+    sink_report(
+        config.result_format,
+        &config.anchors,
+        &diff::Changes::new(diff::Kind::Anchors, anchors),
+    )
+    .await
+}
+
 #[async_trait]
 pub trait Sink: Send + Sync {
     /// Initializes this sink.
@@ -182,6 +526,13 @@ pub trait Sink: Send + Sync {
     where
         Self: Sized;
 
+    /// Sets the cached anchors of remote pages,
+    /// to annotate the fragments of links to them with.
+    /// This will be called at most once,
+    /// right after [`Self::init`].
+    /// Sinks that do not write these annotations ignore it.
+    fn set_remote_anchors(&mut self, _remote_anchors: RemoteAnchors) {}
+
     /// Writes-out an extracted link.
     ///
     /// # Errors
@@ -226,12 +577,15 @@ struct LinkExtendedRec<'a> {
     src_is_url: bool,
     src_is_local: bool,
     src_is_remote: bool,
+    src_counterpart: Option<String>,
     trg_link: String,
     trg_fragment: Option<&'a str>,
     trg_is_file_system: bool,
     trg_is_url: bool,
     trg_is_local: bool,
     trg_is_remote: bool,
+    trg_counterpart: Option<String>,
+    trg_remote_fragment: Option<RemoteFragment>,
 }
 
 #[derive(Debug, Serialize)]
@@ -250,7 +604,12 @@ enum LinkRec<'a> {
 }
 
 impl<'a> LinkRec<'a> {
-    fn new(lnk: &'a Link, extended: bool) -> Self {
+    fn new(
+        lnk: &'a Link,
+        extended: bool,
+        mappings: &[UrlMapping],
+        remote_anchors: &RemoteAnchors,
+    ) -> Self {
         if extended {
             Self::Extended(LinkExtendedRec {
                 src_file: lnk.source.file.to_string(),
@@ -260,12 +619,21 @@ impl<'a> LinkRec<'a> {
                 src_is_url: lnk.source.file.is_url(),
                 src_is_local: lnk.source.file.is_local(),
                 src_is_remote: lnk.source.file.is_remote(),
+                src_counterpart: url_mapping::file_counterpart(mappings, &lnk.source.file)
+                    .map(|file| file.to_string()),
                 trg_link: lnk.target.without_fragment().to_string(),
                 trg_fragment: lnk.target.fragment(),
                 trg_is_file_system: lnk.target.is_file_system(),
                 trg_is_url: lnk.target.is_url(),
                 trg_is_local: lnk.target.is_local(),
                 trg_is_remote: lnk.target.is_remote(),
+                trg_counterpart: url_mapping::target_counterpart(
+                    mappings,
+                    &lnk.source.file,
+                    &lnk.target,
+                )
+                .map(|target| target.to_string()),
+                trg_remote_fragment: remote_anchors.fragment(&lnk.target),
             })
         } else {
             Self::Simple(LinkSimpleRec {
@@ -301,12 +669,15 @@ struct LinkExtendedOwnedRec {
     src_is_url: bool,
     src_is_local: bool,
     src_is_remote: bool,
+    src_counterpart: Option<String>,
     trg_link: String,
     trg_fragment: Option<String>,
     trg_is_file_system: bool,
     trg_is_url: bool,
     trg_is_local: bool,
     trg_is_remote: bool,
+    trg_counterpart: Option<String>,
+    trg_remote_fragment: Option<RemoteFragment>,
 }
 
 #[derive(Debug, Serialize)]
@@ -325,7 +696,12 @@ enum LinkOwnedRec {
 }
 
 impl LinkOwnedRec {
-    fn new(lnk: &Link, extended: bool) -> Self {
+    fn new(
+        lnk: &Link,
+        extended: bool,
+        mappings: &[UrlMapping],
+        remote_anchors: &RemoteAnchors,
+    ) -> Self {
         if extended {
             Self::Extended(LinkExtendedOwnedRec {
                 src_file: lnk.source.file.to_string(),
@@ -335,12 +711,21 @@ impl LinkOwnedRec {
                 src_is_url: lnk.source.file.is_url(),
                 src_is_local: lnk.source.file.is_local(),
                 src_is_remote: lnk.source.file.is_remote(),
+                src_counterpart: url_mapping::file_counterpart(mappings, &lnk.source.file)
+                    .map(|file| file.to_string()),
                 trg_link: lnk.target.without_fragment().to_string(),
                 trg_fragment: lnk.target.fragment().map(ToOwned::to_owned),
                 trg_is_file_system: lnk.target.is_file_system(),
                 trg_is_url: lnk.target.is_url(),
                 trg_is_local: lnk.target.is_local(),
                 trg_is_remote: lnk.target.is_remote(),
+                trg_counterpart: url_mapping::target_counterpart(
+                    mappings,
+                    &lnk.source.file,
+                    &lnk.target,
+                )
+                .map(|target| target.to_string()),
+                trg_remote_fragment: remote_anchors.fragment(&lnk.target),
             })
         } else {
             Self::Simple(LinkSimpleOwnedRec {
@@ -376,6 +761,7 @@ struct AnchorExtendedRec<'a> {
     src_is_url: bool,
     src_is_local: bool,
     src_is_remote: bool,
+    src_counterpart: Option<String>,
     name: &'a str,
     r#type: anchor::Type,
 }
@@ -407,7 +793,7 @@ impl Serialize for AnchorRec<'_> {
 }
 
 impl<'a> AnchorRec<'a> {
-    fn new(anchor: &'a Anchor, extended: bool) -> Self {
+    fn new(anchor: &'a Anchor, extended: bool, mappings: &[UrlMapping]) -> Self {
         if extended {
             Self::Extended(AnchorExtendedRec {
                 src_file: anchor.source.file.to_string(),
@@ -417,6 +803,8 @@ impl<'a> AnchorRec<'a> {
                 src_is_url: anchor.source.file.is_url(),
                 src_is_local: anchor.source.file.is_local(),
                 src_is_remote: anchor.source.file.is_remote(),
+                src_counterpart: url_mapping::file_counterpart(mappings, &anchor.source.file)
+                    .map(|file| file.to_string()),
                 name: &anchor.name,
                 r#type: anchor.r#type,
             })
@@ -441,6 +829,7 @@ struct AnchorExtendedOwnedRec {
     src_is_url: bool,
     src_is_local: bool,
     src_is_remote: bool,
+    src_counterpart: Option<String>,
     name: String,
     r#type: anchor::Type,
 }
@@ -471,7 +860,7 @@ impl Serialize for AnchorOwnedRec {
 }
 
 impl AnchorOwnedRec {
-    fn new(anchor: &Anchor, extended: bool) -> Self {
+    fn new(anchor: &Anchor, extended: bool, mappings: &[UrlMapping]) -> Self {
         if extended {
             Self::Extended(AnchorExtendedOwnedRec {
                 src_file: anchor.source.file.to_string(),
@@ -481,6 +870,8 @@ impl AnchorOwnedRec {
                 src_is_url: anchor.source.file.is_url(),
                 src_is_local: anchor.source.file.is_local(),
                 src_is_remote: anchor.source.file.is_remote(),
+                src_counterpart: url_mapping::file_counterpart(mappings, &anchor.source.file)
+                    .map(|file| file.to_string()),
                 name: anchor.name.clone(),
                 r#type: anchor.r#type,
             })
@@ -0,0 +1,89 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::orphans::Report;
+
+#[derive(Debug, Serialize)]
+struct Rec<'a> {
+    kind: &'a str,
+    file: &'a str,
+}
+
+const KIND_ORPHAN: &str = "orphan";
+const KIND_UNREACHABLE: &str = "unreachable";
+
+fn recs(report: &Report) -> impl Iterator<Item = Rec<'_>> {
+    report
+        .orphans
+        .iter()
+        .map(|file| Rec {
+            kind: KIND_ORPHAN,
+            file,
+        })
+        .chain(report.unreachable.iter().map(|file| Rec {
+            kind: KIND_UNREACHABLE,
+            file,
+        }))
+}
+
+impl super::Report for Report {
+    const NAME: &'static str = "the report of orphan pages";
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        for rec in recs(self) {
+            let _ = writeln!(text, "{}:{}", rec.kind, rec.file);
+        }
+        text
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = "\n# Orphan Pages\n".to_owned();
+        for (title, files) in [
+            ("Entry Points", &self.entry_points),
+            ("Not Linked to by Any Other Page", &self.orphans),
+            ("Not Reachable from Any Entry Point", &self.unreachable),
+        ] {
+            let _ = write!(text, "\n## {title}\n\n");
+            if files.is_empty() {
+                text.push_str("None.\n");
+            }
+            for file in files {
+                let _ = writeln!(text, "- `{file}`");
+            }
+        }
+        text
+    }
+
+    fn json(&self) -> impl Serialize {
+        self
+    }
+
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send> {
+        Some(recs(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::Report as _;
+
+    #[test]
+    fn text() {
+        let report = Report {
+            entry_points: vec!["README.md".to_owned()],
+            orphans: vec!["docs/b.md".to_owned()],
+            unreachable: vec!["docs/b.md".to_owned(), "docs/c.md".to_owned()],
+        };
+        assert_eq!(
+            report.to_text(),
+            "orphan:docs/b.md\nunreachable:docs/b.md\nunreachable:docs/c.md\n"
+        );
+    }
+}
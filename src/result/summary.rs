@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fmt::{Display, Write as _};
+
+use serde::Serialize;
+
+use crate::summary::Summary;
+
+const fn totals(summary: &Summary) -> [(&'static str, usize); 5] {
+    [
+        ("Files", summary.files),
+        ("Links", summary.links),
+        ("Anchors", summary.anchors),
+        ("Invalid targets", summary.invalid_targets),
+        ("Errors", summary.errors),
+    ]
+}
+
+fn sections(summary: &Summary) -> [(&'static str, Vec<(String, usize)>); 5] {
+    fn owned<K: Display>(counts: impl IntoIterator<Item = (K, usize)>) -> Vec<(String, usize)> {
+        counts
+            .into_iter()
+            .map(|(key, count)| (key.to_string(), count))
+            .collect()
+    }
+    [
+        (
+            "Links per file",
+            owned(summary.links_per_file.iter().map(|(k, v)| (k, *v))),
+        ),
+        (
+            "Links per kind",
+            owned(summary.links_per_kind.iter().map(|(k, v)| (k, *v))),
+        ),
+        (
+            "Links per host",
+            owned(summary.links_per_host.iter().map(|(k, v)| (k, *v))),
+        ),
+        (
+            "Anchors per type",
+            owned(summary.anchors_per_type.iter().map(|(k, v)| (k, *v))),
+        ),
+        ("Top external domains", summary.top_external_domains.clone()),
+    ]
+}
+
+impl super::Report for Summary {
+    const NAME: &'static str = "the summary";
+
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (title, count) in totals(self) {
+            let _ = writeln!(text, "{:<16} {count}", format!("{title}:"));
+        }
+        for (title, counts) in sections(self) {
+            let _ = write!(text, "\n{title}:\n");
+            for (key, count) in counts {
+                let _ = writeln!(text, "  {count:>6}  {key}");
+            }
+        }
+        text
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut text = "\n# Summary\n\n| | Count |\n| --- | --- |\n".to_owned();
+        for (title, count) in totals(self) {
+            let _ = writeln!(text, "| {title} | {count} |");
+        }
+        for (title, counts) in sections(self) {
+            let _ = write!(text, "\n## {title}\n\n");
+            if counts.is_empty() {
+                text.push_str("None.\n");
+                continue;
+            }
+            text.push_str("| | Count |\n| --- | --- |\n");
+            for (key, count) in counts {
+                let _ = writeln!(text, "| `{key}` | {count} |");
+            }
+        }
+        text
+    }
+
+    fn json(&self) -> impl Serialize {
+        self
+    }
+
+    /// The summary has no flat form,
+    /// so only the text, Markdown and JSON formats are supported.
+    fn recs(&self) -> Option<impl Iterator<Item = impl Serialize + Send> + Send> {
+        None::<std::iter::Empty<()>>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::Report as _;
+
+    #[test]
+    fn text() {
+        let summary = Summary {
+            files: 1,
+            links: 2,
+            links_per_file: [("README.md".to_owned(), 2)].into(),
+            top_external_domains: vec![("example.org".to_owned(), 2)],
+            ..Summary::default()
+        };
+        assert_eq!(
+            summary.to_text(),
+            "Files:           1
+Links:           2
+Anchors:         0
+Invalid targets: 0
+Errors:          0
+
+Links per file:
+       2  README.md
+
+Links per kind:
+
+Links per host:
+
+Anchors per type:
+
+Top external domains:
+       2  example.org
+"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Programming-/Configuration-language definitions,
+//! as far as they are required for finding comments in source code.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// The comment syntax of a (programming-) language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Language {
+    /// A short, lower-case identifier, e.g. "rust"
+    pub name: String,
+    /// The (lower-case) file extensions used by sources in this language
+    pub extensions: Vec<String>,
+    /// Tokens starting a comment that extends to the end of the line, e.g. "//"
+    pub line_comments: Vec<String>,
+    /// Pairs of tokens starting and ending a block comment, e.g. ("/*", "*/")
+    pub block_comments: Vec<(String, String)>,
+    /// Characters delimiting string literals, e.g. '"';
+    /// comment tokens within string literals are ignored
+    pub string_quotes: Vec<char>,
+}
+
+fn strs(items: &[&str]) -> Vec<String> {
+    items.iter().map(|&item| item.to_owned()).collect()
+}
+
+fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .map(|&(start, end)| (start.to_owned(), end.to_owned()))
+        .collect()
+}
+
+impl Language {
+    fn new(
+        name: &str,
+        extensions: &[&str],
+        line_comments: &[&str],
+        block_comments: &[(&str, &str)],
+        string_quotes: &[char],
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            extensions: strs(extensions),
+            line_comments: strs(line_comments),
+            block_comments: pairs(block_comments),
+            string_quotes: string_quotes.to_vec(),
+        }
+    }
+
+    /// The languages known out of the box.
+    #[must_use]
+    pub fn builtins() -> Vec<Self> {
+        vec![
+            Self::new("rust", &["rs"], &["//"], &[("/*", "*/")], &['"']),
+            Self::new(
+                "c",
+                &["c", "h", "cc", "cpp", "cxx", "hpp", "hxx"],
+                &["//"],
+                &[("/*", "*/")],
+                &['"'],
+            ),
+            Self::new(
+                "java",
+                &["java", "kt", "kts", "scala", "groovy", "cs"],
+                &["//"],
+                &[("/*", "*/")],
+                &['"'],
+            ),
+            Self::new(
+                "javascript",
+                &["js", "mjs", "cjs", "jsx", "ts", "tsx"],
+                &["//"],
+                &[("/*", "*/")],
+                &['"', '\'', '`'],
+            ),
+            Self::new("go", &["go"], &["//"], &[("/*", "*/")], &['"', '`']),
+            Self::new("shell", &["sh", "bash", "zsh"], &["#"], &[], &['"', '\'']),
+            Self::new(
+                "python",
+                &["py"],
+                &["#"],
+                &[("\"\"\"", "\"\"\""), ("'''", "'''")],
+                &['"', '\''],
+            ),
+            Self::new("ruby", &["rb"], &["#"], &[], &['"', '\'']),
+            Self::new("toml", &["toml"], &["#"], &[], &['"', '\'']),
+            Self::new("yaml", &["yml", "yaml"], &["#"], &[], &['"', '\'']),
+            Self::new("css", &["css", "scss"], &[], &[("/*", "*/")], &['"', '\'']),
+            Self::new("sql", &["sql"], &["--"], &[("/*", "*/")], &['\'']),
+            Self::new("lua", &["lua"], &["--"], &[("--[[", "]]")], &['"', '\'']),
+        ]
+    }
+
+    /// The names of the languages known out of the box.
+    #[must_use]
+    pub fn builtin_names() -> Vec<String> {
+        Self::builtins()
+            .into_iter()
+            .map(|language| language.name)
+            .collect()
+    }
+
+    /// Returns the builtin language with the given name, if any.
+    #[must_use]
+    pub fn builtin(name: &str) -> Option<Self> {
+        Self::builtins()
+            .into_iter()
+            .find(|language| language.name == name)
+    }
+
+    /// Finds the language used by a file,
+    /// judging by its (case-insensitive) file extension.
+    #[must_use]
+    pub fn find_by_file_name<'a>(languages: &'a [Self], file_name: &str) -> Option<&'a Self> {
+        let ext = file_name.rsplit_once('.')?.1.to_lowercase();
+        languages
+            .iter()
+            .find(|language| language.extensions.contains(&ext))
+    }
+
+    /// Finds the byte ranges of the contents of all comments in `source`,
+    /// excluding the comment delimiters.
+    #[must_use]
+    pub fn comment_ranges(&self, source: &str) -> Vec<Range<usize>> {
+        // Longer tokens first, so e.g. "--[[" wins over "--"
+        let mut block_comments: Vec<&(String, String)> = self.block_comments.iter().collect();
+        block_comments.sort_by_key(|(start, _end)| std::cmp::Reverse(start.len()));
+        let mut line_comments: Vec<&String> = self.line_comments.iter().collect();
+        line_comments.sort_by_key(|start| std::cmp::Reverse(start.len()));
+
+        let mut ranges = vec![];
+        let mut idx = 0;
+        'outer: while idx < source.len() {
+            let rest = &source[idx..];
+            for (start, end) in &block_comments {
+                if rest.starts_with(start.as_str()) {
+                    let cont_start = idx + start.len();
+                    let (cont_end, next) = source[cont_start..]
+                        .find(end.as_str())
+                        .map_or((source.len(), source.len()), |end_idx| {
+                            (cont_start + end_idx, cont_start + end_idx + end.len())
+                        });
+                    ranges.push(cont_start..cont_end);
+                    idx = next;
+                    continue 'outer;
+                }
+            }
+            for start in &line_comments {
+                if rest.starts_with(start.as_str()) {
+                    let cont_start = idx + start.len();
+                    let cont_end = source[cont_start..]
+                        .find('\n')
+                        .map_or(source.len(), |nl_idx| cont_start + nl_idx);
+                    ranges.push(cont_start..cont_end);
+                    idx = cont_end;
+                    continue 'outer;
+                }
+            }
+            let Some(chr) = rest.chars().next() else {
+                break;
+            };
+            idx += chr.len_utf8();
+            if self.string_quotes.contains(&chr) {
+                // Skip the string literal, honoring backslash escapes
+                let mut escaped = false;
+                for (lit_idx, lit_chr) in source[idx..].char_indices() {
+                    if escaped {
+                        escaped = false;
+                    } else if lit_chr == '\\' {
+                        escaped = true;
+                    } else if lit_chr == chr || lit_chr == '\n' {
+                        idx += lit_idx + lit_chr.len_utf8();
+                        continue 'outer;
+                    }
+                }
+                idx = source.len();
+            }
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comments(lang: &str, source: &str) -> Vec<String> {
+        Language::builtin(lang)
+            .expect("builtin language")
+            .comment_ranges(source)
+            .into_iter()
+            .map(|range| source[range].to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn rust() {
+        let source = "/// See https://a.org\nlet s = \"// no comment\"; /* b */ // c";
+        assert_eq!(
+            vec!["/ See https://a.org", " b ", " c"],
+            comments("rust", source)
+        );
+    }
+
+    #[test]
+    fn shell() {
+        let source = "#!/bin/sh\necho 'not # a comment' \"nor # this\" # but this";
+        assert_eq!(vec!["!/bin/sh", " but this"], comments("shell", source));
+    }
+
+    #[test]
+    fn lua_longest_token_first() {
+        assert_eq!(vec!["a", " b"], comments("lua", "--[[a]]\n-- b"));
+    }
+
+    #[test]
+    fn unterminated_block() {
+        assert_eq!(vec![" a\nb"], comments("c", "/* a\nb"));
+    }
+
+    #[test]
+    fn find_by_file_name() {
+        let languages = Language::builtins();
+        assert_eq!(
+            Language::find_by_file_name(&languages, "src/Main.JAVA").map(|lang| lang.name.as_str()),
+            Some("java")
+        );
+        assert!(Language::find_by_file_name(&languages, "LICENSE").is_none());
+    }
+}
@@ -3,11 +3,15 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 
-use async_std::io;
+use async_std::{fs, io, path::Path};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+use url::Url;
 
-use crate::{anchor::Anchor, config::Tool as Config};
+use crate::{anchor::Anchor, config::Tool as Config, link::Target};
 
 /// If a URL is not stored in the map (the URL does not appear as a key),
 /// it means that URL has not yet been checked.
@@ -29,6 +33,140 @@ pub type AnchorTargets = Option<Vec<Anchor>>;
 /// If the Vec is empty, it means that the document was parsed, but no anchors were found.
 pub type RemoteCache = HashMap<url::Url, io::Result<AnchorTargets>>;
 
+/// How a [`RemoteCache`] entry is stored on disk.
+#[derive(Debug, Serialize, Deserialize)]
+enum CacheEntry {
+    /// The error message of a failed check
+    Unavailable(String),
+    Available(AnchorTargets),
+}
+
+/// Parses a [`RemoteCache`] from JSON,
+/// as written by [`remote_cache_to_json`].
+///
+/// # Errors
+///
+/// If the JSON is malformed.
+pub fn remote_cache_from_json(json: &str) -> serde_json::Result<RemoteCache> {
+    let entries: BTreeMap<Url, CacheEntry> = serde_json::from_str(json)?;
+    Ok(entries
+        .into_iter()
+        .map(|(url, entry)| {
+            let res = match entry {
+                CacheEntry::Unavailable(msg) => Err(io::Error::other(msg)),
+                CacheEntry::Available(anchors) => Ok(anchors),
+            };
+            (url, res)
+        })
+        .collect())
+}
+
+/// Serializes a [`RemoteCache`] to JSON, sorted by URL.
+/// Errors are stored as their message only.
+///
+/// # Errors
+///
+/// If serialization fails.
+pub fn remote_cache_to_json(cache: &RemoteCache) -> serde_json::Result<String> {
+    let entries: BTreeMap<&Url, CacheEntry> = cache
+        .iter()
+        .map(|(url, res)| {
+            let entry = match res {
+                Err(err) => CacheEntry::Unavailable(err.to_string()),
+                Ok(anchors) => CacheEntry::Available(anchors.clone()),
+            };
+            (url, entry)
+        })
+        .collect();
+    serde_json::to_string_pretty(&entries)
+}
+
+/// How the fragment of a link to a remote page
+/// relates to the anchors cached for that page.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RemoteFragment {
+    /// The page has an anchor matching the fragment
+    Found,
+    /// The page was parsed, but has no anchor matching the fragment
+    Missing,
+    /// The page was checked, but was not available
+    Unavailable,
+    /// The page is available, but its anchors were not parsed
+    Unparsed,
+}
+
+impl fmt::Display for RemoteFragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Found => write!(f, "found"),
+            Self::Missing => write!(f, "missing"),
+            Self::Unavailable => write!(f, "unavailable"),
+            Self::Unparsed => write!(f, "unparsed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CachedPage {
+    Unavailable,
+    Unparsed,
+    Parsed(HashSet<String>),
+}
+
+/// A snapshot of the anchor names of a [`RemoteCache`],
+/// to look up the fragments of links in.
+#[derive(Debug, Clone, Default)]
+pub struct RemoteAnchors(HashMap<Url, CachedPage>);
+
+impl From<&RemoteCache> for RemoteAnchors {
+    fn from(cache: &RemoteCache) -> Self {
+        Self(
+            cache
+                .iter()
+                .map(|(url, res)| {
+                    let page = match res {
+                        Err(_) => CachedPage::Unavailable,
+                        Ok(None) => CachedPage::Unparsed,
+                        Ok(Some(anchors)) => CachedPage::Parsed(
+                            anchors.iter().map(|anchor| anchor.name.clone()).collect(),
+                        ),
+                    };
+                    (url.clone(), page)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl RemoteAnchors {
+    /// How the fragment of `target` relates to the cached anchors
+    /// of the page it points to.
+    /// Returns `None` if `target` is no URL with a fragment,
+    /// or if its page is not cached.
+    #[must_use]
+    pub fn fragment(&self, target: &Target) -> Option<RemoteFragment> {
+        let Target::Http(url) = target else {
+            return None;
+        };
+        let fragment = url.fragment().filter(|frag| !frag.is_empty())?;
+        let mut page = url.clone();
+        page.set_fragment(None);
+        Some(match self.0.get(&page)? {
+            CachedPage::Unavailable => RemoteFragment::Unavailable,
+            CachedPage::Unparsed => RemoteFragment::Unparsed,
+            CachedPage::Parsed(names)
+                if names.contains(fragment)
+                    || names
+                        .contains(percent_decode_str(fragment).decode_utf8_lossy().as_ref()) =>
+            {
+                RemoteFragment::Found
+            }
+            CachedPage::Parsed(_) => RemoteFragment::Missing,
+        })
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct State {
     pub config: Config,
@@ -43,4 +181,110 @@ impl State {
             remote_cache: RemoteCache::new(),
         }
     }
+
+    /// Stores the result of checking the remote page at `url`,
+    /// replacing any previous entry.
+    /// This is meant to be used by checkers that embed this library.
+    /// The fragment of `url` is ignored.
+    pub fn cache_remote(&mut self, mut url: Url, result: io::Result<AnchorTargets>) {
+        url.set_fragment(None);
+        self.remote_cache.insert(url, result);
+    }
+
+    /// Loads cached remote pages from a JSON file
+    /// written by [`Self::save_remote_cache`].
+    /// Entries already in the cache take precedence over loaded ones.
+    /// A missing file is treated as an empty cache.
+    ///
+    /// # Errors
+    ///
+    /// If the file exists but could not be read or parsed.
+    pub async fn load_remote_cache(&mut self, path: &Path) -> io::Result<()> {
+        if !path.exists().await {
+            return Ok(());
+        }
+        let json = fs::read_to_string(path).await?;
+        for (url, res) in remote_cache_from_json(&json)? {
+            self.remote_cache.entry(url).or_insert(res);
+        }
+        Ok(())
+    }
+
+    /// Saves the cached remote pages to a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be written.
+    pub async fn save_remote_cache(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, remote_cache_to_json(&self.remote_cache)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anchor::Type;
+    use crate::link::{FileLoc, Locator, Position};
+    use std::sync::Arc;
+
+    fn anchor(page: &Url, name: &str) -> Anchor {
+        Anchor {
+            source: Locator {
+                file: Arc::new(FileLoc::Url(page.clone())),
+                pos: Position { line: 1, column: 1 },
+            },
+            name: name.to_owned(),
+            r#type: Type::TitleAuto,
+        }
+    }
+
+    #[test]
+    fn remote_fragment_names() {
+        for value in [
+            RemoteFragment::Found,
+            RemoteFragment::Missing,
+            RemoteFragment::Unavailable,
+            RemoteFragment::Unparsed,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&value).expect("Serializable"),
+                format!("\"{value}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn json_round_trip_and_lookup() {
+        let page = Url::parse("https://example.org/guide.html").expect("Valid URL");
+        let gone = Url::parse("https://example.org/gone.html").expect("Valid URL");
+        let mut state = State::default();
+        state.cache_remote(
+            Url::parse("https://example.org/guide.html#ignored").expect("Valid URL"),
+            Ok(Some(vec![anchor(&page, "install"), anchor(&page, "café")])),
+        );
+        state.cache_remote(gone, Err(io::Error::other("404 Not Found")));
+        let json = remote_cache_to_json(&state.remote_cache).expect("Serializable");
+        let loaded = remote_cache_from_json(&json).expect("Parsable");
+        assert_eq!(loaded.len(), 2);
+        let remote_anchors = RemoteAnchors::from(&loaded);
+        let lookup = |link: &str| remote_anchors.fragment(&Target::from(link));
+        assert_eq!(
+            lookup("https://example.org/guide.html#install"),
+            Some(RemoteFragment::Found)
+        );
+        assert_eq!(
+            lookup("https://example.org/guide.html#caf%C3%A9"),
+            Some(RemoteFragment::Found)
+        );
+        assert_eq!(
+            lookup("https://example.org/guide.html#usage"),
+            Some(RemoteFragment::Missing)
+        );
+        assert_eq!(
+            lookup("https://example.org/gone.html#x"),
+            Some(RemoteFragment::Unavailable)
+        );
+        assert_eq!(lookup("https://example.org/guide.html"), None);
+        assert_eq!(lookup("https://example.org/other.html#x"), None);
+    }
 }
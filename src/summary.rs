@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Summary statistics over the extracted links and anchors.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::anchor::Anchor;
+use crate::graph::document_id;
+use crate::link::{FileLoc, Link};
+
+/// How many of the most linked external domains to list.
+pub const TOP_DOMAINS: usize = 10;
+
+/// Counts of links and anchors, overall and per category.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize)]
+pub struct Summary {
+    /// The number of scanned markup files
+    pub files: usize,
+    pub links: usize,
+    pub anchors: usize,
+    /// The number of links whose target could not be parsed
+    pub invalid_targets: usize,
+    /// The number of errors that occurred while extracting
+    pub errors: usize,
+    /// Including scanned files without any links
+    pub links_per_file: BTreeMap<String, usize>,
+    /// Keyed by [`crate::link::TargetKind::as_str`]
+    pub links_per_kind: BTreeMap<&'static str, usize>,
+    /// Including e-mail domains
+    pub links_per_host: BTreeMap<String, usize>,
+    /// Keyed by the name of the [`crate::anchor::Type`]
+    pub anchors_per_type: BTreeMap<String, usize>,
+    /// The remote hosts linked to most, most linked first
+    pub top_external_domains: Vec<(String, usize)>,
+}
+
+impl Summary {
+    /// Counts the links and anchors found in `scanned_files`.
+    #[must_use]
+    pub fn new(
+        scanned_files: &[Arc<FileLoc>],
+        links: &[Link],
+        anchors: &[Anchor],
+        errors: usize,
+    ) -> Self {
+        let mut summary = Self {
+            files: scanned_files.len(),
+            links: links.len(),
+            anchors: anchors.len(),
+            errors,
+            links_per_file: scanned_files
+                .iter()
+                .map(|file| (document_id(file), 0))
+                .collect(),
+            ..Self::default()
+        };
+        let mut external: BTreeMap<&str, usize> = BTreeMap::new();
+        for link in links {
+            *summary
+                .links_per_file
+                .entry(document_id(&link.source.file))
+                .or_default() += 1;
+            *summary
+                .links_per_kind
+                .entry(link.target.kind().as_str())
+                .or_default() += 1;
+            if link.is_invalid() {
+                summary.invalid_targets += 1;
+            }
+            if let Some(host) = link.target.host() {
+                *summary.links_per_host.entry(host.to_owned()).or_default() += 1;
+                if link.target.is_remote() {
+                    *external.entry(host).or_default() += 1;
+                }
+            }
+        }
+        for anchor in anchors {
+            *summary
+                .anchors_per_type
+                .entry(anchor.r#type.to_string())
+                .or_default() += 1;
+        }
+        let mut domains: Vec<(&str, usize)> = external.into_iter().collect();
+        // Stable, so equally often linked domains stay sorted by name
+        domains.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        summary.top_external_domains = domains
+            .into_iter()
+            .take(TOP_DOMAINS)
+            .map(|(host, count)| (host.to_owned(), count))
+            .collect();
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::Position;
+
+    #[test]
+    fn count() {
+        let readme = Arc::new(FileLoc::System("README.md".parse().expect("Valid path")));
+        let empty = Arc::new(FileLoc::System("empty.md".parse().expect("Valid path")));
+        let link =
+            |target: &str| Link::new(readme.clone(), Position { line: 1, column: 1 }, target);
+        let links = [
+            link("https://b.example.org/x"),
+            link("https://a.example.org/y"),
+            link("https://b.example.org/z"),
+            link("mailto:info@example.org"),
+            link("docs/setup.md"),
+        ];
+        let summary = Summary::new(&[readme.clone(), empty], &links, &[], 1);
+        assert_eq!(summary.files, 2);
+        assert_eq!(summary.links, 5);
+        assert_eq!(summary.errors, 1);
+        assert_eq!(summary.links_per_file.get("README.md"), Some(&5));
+        assert_eq!(summary.links_per_file.get("empty.md"), Some(&0));
+        assert_eq!(summary.links_per_kind.get("http"), Some(&3));
+        assert_eq!(summary.links_per_host.get("example.org"), Some(&1));
+        assert_eq!(
+            summary.top_external_domains,
+            vec![
+                ("b.example.org".to_owned(), 2),
+                ("a.example.org".to_owned(), 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Suppression of links and anchors through directives
+//! in (HTML) comments within the markup itself,
+//! e.g. `<!-- mle-ignore-next-line -->`.
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::anchor::Anchor;
+use crate::extractors;
+use crate::link::{Link, Position};
+
+/// Matches an HTML comment that contains only a directive-like word
+static DIRECTIVE_COMMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<!--[ \t\r\n]*(mle-[a-z-]+)[ \t\r\n]*-->").unwrap());
+
+/// An instruction to this tool,
+/// written as the sole content of a comment in the markup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Directive {
+    /// `mle-ignore-next-line`:
+    /// Ignore links and anchors on the line following the comment.
+    IgnoreNextLine,
+    /// `mle-disable`:
+    /// Ignore links and anchors from here on, until `mle-enable`.
+    Disable,
+    /// `mle-enable`:
+    /// Ends an `mle-disable` section.
+    Enable,
+    /// `mle-ignore-file`:
+    /// Ignore all links and anchors in the file.
+    IgnoreFile,
+}
+
+impl Directive {
+    /// Parses the text of a comment (without `<!--` and `-->`).
+    /// Returns `None` for regular comments.
+    #[must_use]
+    pub fn parse(comment: &str) -> Option<Self> {
+        let word = comment.trim_matches([' ', '\t', '\r', '\n']);
+        match word {
+            "mle-ignore-next-line" => Some(Self::IgnoreNextLine),
+            "mle-disable" => Some(Self::Disable),
+            "mle-enable" => Some(Self::Enable),
+            "mle-ignore-file" => Some(Self::IgnoreFile),
+            _ => {
+                if word.starts_with("mle-") && !word.contains([' ', '\t', '\r', '\n']) {
+                    log::warn!("Unknown directive in comment: '{word}'");
+                }
+                None
+            }
+        }
+    }
+}
+
+const fn key(pos: &Position) -> (usize, usize) {
+    (pos.line, pos.column)
+}
+
+/// Collects the directives found in a markup file,
+/// and decides which links and anchors they suppress.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    ignore_file: bool,
+    ignored_lines: HashSet<usize>,
+    /// Where disabled sections start, and - if already known - where they end
+    disabled: Vec<(Position, Option<Position>)>,
+}
+
+impl Suppressions {
+    /// Registers a directive, found in a comment
+    /// spanning from `start` to `end`.
+    pub fn add(&mut self, directive: Directive, start: &Position, end: &Position) {
+        match directive {
+            Directive::IgnoreNextLine => {
+                self.ignored_lines.insert(end.line + 1);
+            }
+            Directive::Disable => {
+                if !self
+                    .disabled
+                    .iter()
+                    .any(|(_, section_end)| section_end.is_none())
+                {
+                    self.disabled.push((end.clone(), None));
+                }
+            }
+            Directive::Enable => {
+                if let Some((_, section_end @ None)) = self.disabled.last_mut() {
+                    *section_end = Some(start.clone());
+                }
+            }
+            Directive::IgnoreFile => self.ignore_file = true,
+        }
+    }
+
+    /// Registers all the directives within HTML comments in `content`,
+    /// which itself starts at `start`.
+    pub fn add_from_html(&mut self, content: &str, start: &Position) {
+        for caps in DIRECTIVE_COMMENT.captures_iter(content) {
+            let (Some(comment), Some(word)) = (caps.get(0), caps.get(1)) else {
+                continue;
+            };
+            let Some(directive) = Directive::parse(word.as_str()) else {
+                continue;
+            };
+            let comment_start = extractors::pos_from_byte_idx(content, comment.start());
+            let comment_end = extractors::pos_from_byte_idx(content, comment.end());
+            self.add(
+                directive,
+                &(comment_start + start - Position { line: 1, column: 1 }),
+                &(comment_end + start - Position { line: 1, column: 1 }),
+            );
+        }
+    }
+
+    /// Whether a link or anchor found at `pos` is suppressed.
+    #[must_use]
+    pub fn is_suppressed(&self, pos: &Position) -> bool {
+        self.ignore_file
+            || self.ignored_lines.contains(&pos.line)
+            || self.disabled.iter().any(|(section_start, section_end)| {
+                key(section_start) <= key(pos)
+                    && section_end.as_ref().is_none_or(|end| key(pos) < key(end))
+            })
+    }
+
+    /// Passes on all links and anchors that are not suppressed.
+    pub async fn emit<LR: AsyncFnMut(Link), AR: AsyncFnMut(Anchor)>(
+        &self,
+        links: Vec<Link>,
+        anchors: Vec<Anchor>,
+        links_receiver: &mut LR,
+        anchors_receiver: &mut AR,
+    ) {
+        for link in links {
+            if self.is_suppressed(&link.source.pos) {
+                log::debug!("Suppressed link by directive: {link}");
+            } else {
+                links_receiver(link).await;
+            }
+        }
+        for anchor in anchors {
+            if self.is_suppressed(&anchor.source.pos) {
+                log::debug!("Suppressed anchor by directive: {anchor}");
+            } else {
+                anchors_receiver(anchor).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn pos(line: usize, column: usize) -> Position {
+        Position { line, column }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Directive::parse(" mle-ignore-next-line "),
+            Some(Directive::IgnoreNextLine)
+        );
+        assert_eq!(Directive::parse("mle-disable"), Some(Directive::Disable));
+        assert_eq!(Directive::parse("just a comment"), None);
+    }
+
+    #[test]
+    fn sections() {
+        let mut suppressions = Suppressions::default();
+        suppressions.add_from_html(
+            "<!-- mle-ignore-next-line -->\nx\n<!-- mle-disable --> y\nz <!-- mle-enable --> w",
+            &pos(3, 1),
+        );
+        assert!(!suppressions.is_suppressed(&pos(3, 5)));
+        assert!(suppressions.is_suppressed(&pos(4, 1)));
+        assert!(!suppressions.is_suppressed(&pos(5, 1)));
+        assert!(suppressions.is_suppressed(&pos(5, 22)));
+        assert!(suppressions.is_suppressed(&pos(6, 1)));
+        assert!(!suppressions.is_suppressed(&pos(6, 23)));
+        suppressions.add(Directive::IgnoreFile, &pos(9, 1), &pos(9, 20));
+        assert!(suppressions.is_suppressed(&pos(1, 1)));
+    }
+}
@@ -0,0 +1,326 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Mappings between local paths and the public URLs
+//! they are published under,
+//! e.g. `site/guide/install.md` <-> `https://docs.example.org/guide/install.html`.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use relative_path::{RelativePath, RelativePathBuf};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::link::{FileLoc, FileSystemLoc, FileSystemTarget, Target};
+
+/// Rewrites a local file extension to the one of the published file,
+/// e.g. `md=html`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionMapping {
+    /// The extension of the local file, without the leading `.`
+    pub local: String,
+    /// The extension of the published file, without the leading `.`
+    pub public: String,
+}
+
+impl fmt::Display for ExtensionMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.local, self.public)
+    }
+}
+
+impl FromStr for ExtensionMapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (local, public) = s.split_once('=').ok_or_else(|| {
+            format!("Missing '=' in extension mapping '{s}'; use LOCAL_EXT=PUBLIC_EXT")
+        })?;
+        Ok(Self {
+            local: local.trim_start_matches('.').to_owned(),
+            public: public.trim_start_matches('.').to_owned(),
+        })
+    }
+}
+
+impl Serialize for ExtensionMapping {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtensionMapping {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Maps the local directory [`Self::path`]
+/// to the URL [`Self::url`] it is published under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlMapping {
+    /// The local directory, relative to the working directory
+    /// (or to the configuration file it is set in),
+    /// e.g. `site`
+    pub path: RelativePathBuf,
+    /// The URL the directory is published under,
+    /// e.g. `https://docs.example.org/`
+    pub url: Url,
+    /// File extensions that differ between the local and the published files
+    #[serde(default)]
+    pub extensions: Vec<ExtensionMapping>,
+}
+
+impl fmt::Display for UrlMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.path, self.url)?;
+        for extension in &self.extensions {
+            write!(f, ",{extension}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for UrlMapping {
+    type Err = String;
+
+    /// Parses the form `PATH=URL[,LOCAL_EXT=PUBLIC_EXT...]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, rest) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Missing '=' in URL mapping '{s}'; use PATH=URL"))?;
+        let mut parts = rest.split(',');
+        let url_str = parts.next().unwrap_or_default();
+        Ok(Self {
+            path: RelativePathBuf::from(path),
+            url: Url::parse(url_str)
+                .map_err(|err| format!("Invalid URL in URL mapping '{s}': {err}"))?,
+            extensions: parts
+                .map(ExtensionMapping::from_str)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl UrlMapping {
+    fn url_base(&self) -> &str {
+        self.url.as_str().trim_end_matches('/')
+    }
+
+    /// The public URL of the local file at `path`,
+    /// if it is within [`Self::path`].
+    #[must_use]
+    pub fn to_url(&self, path: &RelativePath) -> Option<Url> {
+        let normalized = path.normalize();
+        let rest = normalized.strip_prefix(self.path.normalize()).ok()?;
+        let rewritten = rest
+            .extension()
+            .and_then(|ext| self.extensions.iter().find(|mapping| mapping.local == ext))
+            .map_or_else(
+                || rest.to_relative_path_buf(),
+                |mapping| rest.with_extension(&mapping.public),
+            );
+        Url::parse(&format!("{}/{rewritten}", self.url_base())).ok()
+    }
+
+    /// The local file published under `url`,
+    /// if it is within [`Self::url`].
+    /// The fragment and query of the URL are ignored.
+    #[must_use]
+    pub fn to_path(&self, url: &Url) -> Option<RelativePathBuf> {
+        let mut plain_url = url.clone();
+        plain_url.set_fragment(None);
+        plain_url.set_query(None);
+        let rest = plain_url.as_str().strip_prefix(self.url_base())?;
+        if !(rest.is_empty() || rest.starts_with('/')) {
+            return None;
+        }
+        let rel_rest = RelativePath::new(rest.trim_start_matches('/'));
+        let rewritten = rel_rest
+            .extension()
+            .and_then(|ext| self.extensions.iter().find(|mapping| mapping.public == ext))
+            .map_or_else(
+                || rel_rest.to_relative_path_buf(),
+                |mapping| rel_rest.with_extension(&mapping.local),
+            );
+        Some(self.path.join_normalized(rewritten))
+    }
+}
+
+fn path_to_url(mappings: &[UrlMapping], path: &RelativePath) -> Option<Url> {
+    mappings.iter().find_map(|mapping| mapping.to_url(path))
+}
+
+fn url_to_path(mappings: &[UrlMapping], url: &Url) -> Option<RelativePathBuf> {
+    mappings.iter().find_map(|mapping| mapping.to_path(url))
+}
+
+/// The path of `loc` relative to the working directory,
+/// which the local directories of the mappings are relative to;
+/// absolute paths outside of it start with `../`.
+fn relative_to_cwd(loc: &FileSystemLoc) -> Option<RelativePathBuf> {
+    match loc {
+        FileSystemLoc::Relative(path) => Some(path.clone()),
+        FileSystemLoc::Absolute(path) => {
+            let cwd = std::env::current_dir().ok()?;
+            let abs_path: &Path = path.as_ref();
+            cwd.ancestors().enumerate().find_map(|(depth, ancestor)| {
+                let rest =
+                    RelativePathBuf::from_path(abs_path.strip_prefix(ancestor).ok()?).ok()?;
+                Some(RelativePath::new(&"../".repeat(depth)).join_normalized(rest))
+            })
+        }
+    }
+}
+
+/// The public URL of a local markup file, or the local file of a public URL,
+/// according to the first matching mapping.
+/// Absolute paths are matched relative to the working directory.
+#[must_use]
+pub fn file_counterpart(mappings: &[UrlMapping], file: &FileLoc) -> Option<FileLoc> {
+    match file {
+        FileLoc::System(loc) => path_to_url(mappings, &relative_to_cwd(loc)?).map(FileLoc::Url),
+        FileLoc::Url(url) => {
+            url_to_path(mappings, url).map(|path| FileLoc::System(FileSystemLoc::Relative(path)))
+        }
+        FileLoc::GitRev { .. } | FileLoc::Archive { .. } => None,
+    }
+}
+
+/// The public URL of a local link target, or the local file of a public URL,
+/// according to the first matching mapping.
+///
+/// Relative file-system targets are resolved
+/// against the markup file `source` they were found in.
+/// Absolute paths are matched relative to the working directory,
+/// so this also works for targets resolved with [`crate::config::Extractor::site_root`].
+/// The fragment is kept.
+#[must_use]
+pub fn target_counterpart(
+    mappings: &[UrlMapping],
+    source: &FileLoc,
+    target: &Target,
+) -> Option<Target> {
+    match target {
+        Target::FileSystem(FileSystemTarget { file, anchor }) if !file.get_raw().is_empty() => {
+            let target_path = match file {
+                FileSystemLoc::Absolute(_) => relative_to_cwd(file)?,
+                FileSystemLoc::Relative(path) => {
+                    let FileLoc::System(source_loc) = source else {
+                        return None;
+                    };
+                    relative_to_cwd(source_loc)?
+                        .parent()
+                        .unwrap_or_else(|| RelativePath::new(""))
+                        .join_normalized(path)
+                }
+            };
+            let mut url = path_to_url(mappings, &target_path)?;
+            url.set_fragment(anchor.as_deref());
+            Some(Target::Http(url))
+        }
+        Target::Http(url) => {
+            let path = url_to_path(mappings, url)?;
+            Some(Target::FileSystem(FileSystemTarget {
+                file: FileSystemLoc::Relative(path),
+                anchor: url.fragment().map(ToOwned::to_owned),
+            }))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> UrlMapping {
+        "site=https://docs.example.org/,md=html"
+            .parse()
+            .expect("Valid mapping")
+    }
+
+    #[test]
+    fn parse() {
+        let parsed = mapping();
+        assert_eq!(parsed.path, RelativePathBuf::from("site"));
+        assert_eq!(parsed.url.as_str(), "https://docs.example.org/");
+        assert_eq!(parsed.to_string(), "site=https://docs.example.org/,md=html");
+        assert!("site".parse::<UrlMapping>().is_err());
+        assert!("site=no-url".parse::<UrlMapping>().is_err());
+    }
+
+    #[test]
+    fn both_ways() {
+        let mappings = [mapping()];
+        let source = FileLoc::System(FileSystemLoc::Relative("README.md".into()));
+        let url_target = Target::from("https://docs.example.org/guide/install.html#step-2");
+        let local_target = Target::from("site/guide/install.md#step-2");
+        assert_eq!(
+            target_counterpart(&mappings, &source, &url_target),
+            Some(local_target.clone())
+        );
+        assert_eq!(
+            target_counterpart(&mappings, &source, &local_target),
+            Some(url_target)
+        );
+        let page = FileLoc::System(FileSystemLoc::Relative("./site/guide/index.md".into()));
+        assert_eq!(
+            target_counterpart(&mappings, &page, &Target::from("../img/logo.png")),
+            Some(Target::from("https://docs.example.org/img/logo.png"))
+        );
+        assert_eq!(
+            file_counterpart(&mappings, &page).map(|file| file.to_string()),
+            Some("https://docs.example.org/guide/index.html".to_owned())
+        );
+        assert_eq!(
+            target_counterpart(&mappings, &source, &Target::from("https://example.org/")),
+            None
+        );
+    }
+
+    #[test]
+    fn absolute_paths() {
+        let mappings = [mapping()];
+        let cwd = std::env::current_dir().expect("Working directory exists");
+        let abs = |path: &str| FileSystemLoc::Absolute(cwd.join(path).into());
+        let page = FileLoc::System(abs("site/guide/index.md"));
+        assert_eq!(
+            file_counterpart(&mappings, &page).map(|file| file.to_string()),
+            Some("https://docs.example.org/guide/index.html".to_owned())
+        );
+        assert_eq!(
+            target_counterpart(&mappings, &page, &Target::from("../img/logo.png")),
+            Some(Target::from("https://docs.example.org/img/logo.png"))
+        );
+        let readme = FileLoc::System(FileSystemLoc::Relative("README.md".into()));
+        let site_root_target = Target::FileSystem(FileSystemTarget {
+            file: abs("site/guide/install.md"),
+            anchor: Some("step-2".to_owned()),
+        });
+        assert_eq!(
+            target_counterpart(&mappings, &readme, &site_root_target),
+            Some(Target::from(
+                "https://docs.example.org/guide/install.html#step-2"
+            ))
+        );
+        let outside = [UrlMapping {
+            path: RelativePathBuf::from("../site"),
+            ..mapping()
+        }];
+        let outside_page = cwd.parent().map(|parent| {
+            FileLoc::System(FileSystemLoc::Absolute(parent.join("site/a.md").into()))
+        });
+        assert_eq!(
+            outside_page
+                .and_then(|file| file_counterpart(&outside, &file))
+                .map(|file| file.to_string()),
+            Some("https://docs.example.org/a.html".to_owned())
+        );
+    }
+}
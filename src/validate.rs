@@ -0,0 +1,227 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Offline validation of local links:
+//! checks that linked files exist,
+//! and that linked fragments match an anchor found in the same run.
+//!
+//! This never accesses the network;
+//! remote links are not checked at all.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use async_std::path::Path;
+use cli_utils::path_buf::PathBuf;
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+
+use crate::anchor::Anchor;
+use crate::link::{FileLoc, Link, Target};
+
+/// Why a link is considered broken.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Reason {
+    /// The target could not be parsed at all
+    InvalidTarget,
+    /// The linked file or directory does not exist
+    MissingFile,
+    /// The linked file was scanned,
+    /// but contains no anchor matching the fragment of the link
+    MissingAnchor,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTarget => write!(f, "invalid-target"),
+            Self::MissingFile => write!(f, "missing-file"),
+            Self::MissingAnchor => write!(f, "missing-anchor"),
+        }
+    }
+}
+
+/// A link that failed validation.
+#[derive(Debug, Clone)]
+pub struct BrokenLink {
+    pub link: Link,
+    pub reason: Reason,
+}
+
+impl fmt::Display for BrokenLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.reason, self.link)
+    }
+}
+
+/// The canonical local path of a markup file,
+/// if it is a plain file on the local file-system.
+fn local_path(file: &FileLoc, base: &PathBuf) -> Option<String> {
+    if !matches!(file, FileLoc::System(_)) {
+        return None;
+    }
+    let arc_file = Arc::new(file.clone());
+    match arc_file.canonical(base).ok()?.as_ref() {
+        FileLoc::System(loc) => Some(loc.normalized().to_string()),
+        _ => None,
+    }
+}
+
+/// The canonical local path a link points to, and its fragment,
+/// if it points to the local file-system.
+fn local_target(link: &Link, base: &PathBuf) -> Option<(String, Option<String>)> {
+    match &link.target {
+        Target::FileUrl(url) => Some((
+            url.to_file_path().ok()?.display().to_string(),
+            url.fragment().map(ToOwned::to_owned),
+        )),
+        Target::FileSystem(fs_target) => {
+            if fs_target.file.get_raw().is_empty() {
+                // A link to an anchor within the same document
+                return Some((
+                    local_path(&link.source.file, base)?,
+                    fs_target.anchor.clone(),
+                ));
+            }
+            if !matches!(link.source.file.as_ref(), FileLoc::System(_)) {
+                return None;
+            }
+            match link
+                .target
+                .canonical(false, link.source.file.clone(), base)
+                .ok()?
+                .as_ref()
+            {
+                Target::FileSystem(abs_target) => Some((
+                    abs_target.file.normalized().to_string(),
+                    abs_target.anchor.clone(),
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether `anchors` contains `fragment`,
+/// either as it is or percent-decoded
+/// (e.g. `caf%C3%A9` for the anchor `café`).
+fn has_anchor(anchors: &HashSet<&str>, fragment: &str) -> bool {
+    anchors.contains(fragment)
+        || anchors.contains(percent_decode_str(fragment).decode_utf8_lossy().as_ref())
+}
+
+/// Checks all local links, using the anchors found in the same run.
+///
+/// Links pointing to the local file-system are resolved
+/// with [`Target::canonical`], relative to the working directory,
+/// and are checked for whether their file exists.
+/// If the link has a fragment and the linked file is one of `scanned_files`,
+/// the fragment has to match one of its anchors.
+/// Links within git revisions, archives and remote files are not checked.
+///
+/// # Errors
+///
+/// If the working directory could not be determined.
+pub async fn validate(
+    links: &[Link],
+    anchors: &[Anchor],
+    scanned_files: &[Arc<FileLoc>],
+) -> std::io::Result<Vec<BrokenLink>> {
+    let base = PathBuf::from(std::env::current_dir()?);
+    let mut anchors_by_file: HashMap<String, HashSet<&str>> = HashMap::new();
+    for anchor in anchors {
+        if let Some(path) = local_path(&anchor.source.file, &base) {
+            anchors_by_file
+                .entry(path)
+                .or_default()
+                .insert(anchor.name.as_str());
+        }
+    }
+    let scanned_paths: HashSet<String> = scanned_files
+        .iter()
+        .filter_map(|file| local_path(file, &base))
+        .collect();
+
+    let mut broken = vec![];
+    for link in links {
+        if link.is_invalid() {
+            broken.push(BrokenLink {
+                link: link.clone(),
+                reason: Reason::InvalidTarget,
+            });
+            continue;
+        }
+        let Some((path, fragment)) = local_target(link, &base) else {
+            continue;
+        };
+        if !Path::new(&path).exists().await {
+            broken.push(BrokenLink {
+                link: link.clone(),
+                reason: Reason::MissingFile,
+            });
+            continue;
+        }
+        let Some(non_empty_fragment) = fragment.filter(|frag| !frag.is_empty()) else {
+            continue;
+        };
+        match anchors_by_file.get(&path) {
+            Some(file_anchors) if has_anchor(file_anchors, &non_empty_fragment) => (),
+            None if !scanned_paths.contains(&path) => {
+                log::debug!("Not checking fragment of link to a file that was not scanned: {link}");
+            }
+            _ => broken.push(BrokenLink {
+                link: link.clone(),
+                reason: Reason::MissingAnchor,
+            }),
+        }
+    }
+    Ok(broken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::Position;
+
+    #[test]
+    fn reason_names() {
+        for reason in [
+            Reason::InvalidTarget,
+            Reason::MissingFile,
+            Reason::MissingAnchor,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&reason).expect("Serializable"),
+                format!("\"{reason}\"")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_and_file_urls() {
+        let file = Arc::new(FileLoc::System("page.md".parse().expect("Valid path")));
+        let pos = Position { line: 1, column: 1 };
+        let links = [
+            Link::new(
+                file.clone(),
+                pos.clone(),
+                "https://example.invalid/missing.md",
+            ),
+            Link::new(file.clone(), pos.clone(), "file:///mle-nonexistent/file.md"),
+            Link::new(file, pos, "file:///"),
+        ];
+        let broken = validate(&links, &[], &[])
+            .await
+            .expect("Working directory exists");
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].reason, Reason::MissingFile);
+        assert_eq!(
+            broken[0].link.target.to_string(),
+            "file:///mle-nonexistent/file.md"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+#[cfg(test)]
+mod helper;
+
+mod config_file {
+    use crate::helper::{TempDir, run_mle};
+
+    const PAGE: &str = "# Page\n\n[a](https://example.com/a) [b](other.md)\n";
+    const CONFIG: &str = r#"
+[extractor]
+markup_files = ["page.md"]
+ignore_links = ["https://*"]
+"#;
+
+    #[test]
+    fn found_upwards() {
+        let dir = TempDir::new("config-upwards");
+        dir.write("mle.toml", CONFIG);
+        dir.write("page.md", PAGE);
+        let sub_dir = dir.join("sub");
+        std::fs::create_dir(&sub_dir).expect("Failed to create sub dir");
+        assert_eq!(run_mle(&sub_dir, &[]), "L:../page.md:3:28:other.md\n");
+    }
+
+    #[test]
+    fn cli_precedence() {
+        let dir = TempDir::new("config-precedence");
+        dir.write("custom.toml", CONFIG);
+        dir.write("page.md", PAGE);
+        let config_arg = dir.join("custom.toml").display().to_string();
+        assert_eq!(
+            run_mle(&dir, &["--config", &config_arg, "--ignore-links", "*.md"]),
+            format!(
+                "L:{}:3:1:https://example.com/a\n",
+                dir.join("page.md").display()
+            )
+        );
+    }
+
+    #[test]
+    fn print_config() {
+        let dir = TempDir::new("config-print");
+        dir.write("mle.toml", CONFIG);
+        let printed = run_mle(&dir, &["--print-config", "--anchors", "--sniff"]);
+        for expected in [
+            r#"anchors = "-""#,
+            r#"markup_files = ["page.md"]"#,
+            r#"ignore_links = ["https://*"]"#,
+            "sniff_content = true",
+        ] {
+            assert!(
+                printed.lines().any(|line| line == expected),
+                "'{expected}' missing in:\n{printed}"
+            );
+        }
+    }
+
+    #[test]
+    fn negated_flags() {
+        let dir = TempDir::new("config-negated");
+        dir.write(
+            "mle.toml",
+            "result_extended = true\n\n[extractor]\nsniff_content = true\n",
+        );
+        let printed = run_mle(
+            &dir,
+            &["--print-config", "--no-sniff", "--no-result-extended", "-E"],
+        );
+        for expected in ["sniff_content = false", "result_extended = true"] {
+            assert!(
+                printed.lines().any(|line| line == expected),
+                "'{expected}' missing in:\n{printed}"
+            );
+        }
+    }
+}
+
+mod link_filter {
+    use crate::helper::{TempDir, run_mle};
+
+    const PAGE: &str = "[a](https://example.com/a) [b](https://docs.example.com/b.pdf)
+[c](mailto:someone@example.com) [d](other.md)
+";
+
+    #[test]
+    fn cli_flags() {
+        let dir = TempDir::new("link-filter");
+        dir.write("page.md", PAGE);
+        assert_eq!(
+            run_mle(
+                &dir,
+                &[
+                    "page.md",
+                    "--include-kinds",
+                    "http",
+                    "--exclude-regex",
+                    r"\.pdf$"
+                ]
+            ),
+            "L:page.md:1:1:https://example.com/a\n"
+        );
+        assert_eq!(
+            run_mle(
+                &dir,
+                &[
+                    "page.md",
+                    "--exclude-kinds",
+                    "email",
+                    "--locality",
+                    "remote"
+                ]
+            ),
+            "L:page.md:1:1:https://example.com/a
+L:page.md:1:28:https://docs.example.com/b.pdf
+"
+        );
+        assert_eq!(
+            run_mle(
+                &dir,
+                &["page.md", "--exclude-hosts", "docs.*", "example.com"]
+            ),
+            "L:page.md:2:33:other.md\n"
+        );
+    }
+}
+
+mod site_root {
+    use crate::helper::{TempDir, run_mle};
+
+    const PAGE: &str =
+        "[a](/docs/setup.md#install) [b](../img/logo.png) [c](https://example.com/)\n";
+
+    #[test]
+    fn site_root_and_canonical() {
+        let dir = TempDir::new("site-root");
+        dir.write("site/blog/post.md", PAGE);
+
+        let abs_dir = dir.canonicalize().expect("Failed to canonicalize");
+        let abs = abs_dir.display();
+        assert_eq!(
+            run_mle(&abs_dir, &["site/blog/post.md", "--site-root", "./site"]),
+            format!(
+                "L:site/blog/post.md:1:1:{abs}/site/docs/setup.md#install
+L:site/blog/post.md:1:29:../img/logo.png
+L:site/blog/post.md:1:50:https://example.com/
+"
+            )
+        );
+
+        assert_eq!(
+            run_mle(
+                &abs_dir,
+                &["site/blog/post.md", "--site-root", "site", "--canonical"]
+            ),
+            format!(
+                "L:site/blog/post.md:1:1:{abs}/site/docs/setup.md#install
+L:site/blog/post.md:1:29:{abs}/site/img/logo.png
+L:site/blog/post.md:1:50:https://example.com/
+"
+            )
+        );
+    }
+
+    #[test]
+    fn ignore_globs_match_targets_as_written() {
+        let dir = TempDir::new("site-root-ignore");
+        dir.write("README.md", "[x](/img/x.png) [y](docs/y.md)\n");
+        for extra in [&[][..], &["--site-root", "."], &["--canonical"]] {
+            let args = [&["README.md", "--ignore-links", "/img/*"][..], extra].concat();
+            let output = run_mle(&dir, &args);
+            assert!(!output.contains("x.png"), "{extra:?}: {output}");
+            assert!(output.contains("y.md"), "{extra:?}: {output}");
+        }
+    }
+}
+
+mod url_mappings {
+    use crate::helper::{TempDir, run_mle};
+
+    const README: &str =
+        "[a](https://docs.example.org/guide/install.html#step-2) [b](site/index.md)\n";
+
+    #[test]
+    fn extended_json() {
+        let dir = TempDir::new("url-mappings");
+        dir.write("README.md", README);
+        let output = run_mle(
+            &dir,
+            &[
+                "README.md",
+                "--result-format",
+                "json",
+                "--result-extended",
+                "--url-mappings",
+                "site=https://docs.example.org/,md=html",
+            ],
+        );
+        let links: serde_json::Value = serde_json::from_str(&output).expect("Valid JSON");
+        let counterparts: Vec<_> = links
+            .as_array()
+            .expect("An array of links")
+            .iter()
+            .map(|link| (&link["src_counterpart"], &link["trg_counterpart"]))
+            .collect();
+        assert_eq!(
+            counterparts,
+            vec![
+                (
+                    &serde_json::Value::Null,
+                    &serde_json::json!("site/guide/install.md#step-2")
+                ),
+                (
+                    &serde_json::Value::Null,
+                    &serde_json::json!("https://docs.example.org/index.html")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn with_site_root() {
+        let dir = TempDir::new("url-mappings-site-root");
+        dir.write("README.md", "[a](/guide/install.md#step-2)\n");
+        let output = run_mle(
+            &dir,
+            &[
+                "README.md",
+                "--result-format",
+                "json",
+                "--result-extended",
+                "--site-root",
+                "site",
+                "--url-mappings",
+                "site=https://docs.example.org/,md=html",
+            ],
+        );
+        let links: serde_json::Value = serde_json::from_str(&output).expect("Valid JSON");
+        assert_eq!(
+            links[0]["trg_counterpart"],
+            serde_json::json!("https://docs.example.org/guide/install.html#step-2")
+        );
+    }
+}
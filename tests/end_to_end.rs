@@ -37,6 +37,7 @@ async fn end_to_end() {
             links: true,
             anchors: true,
             ignore_links,
+            ..Default::default()
         },
         links: Some(StreamIdent::StdOut),
         anchors: Some(StreamIdent::StdOut),
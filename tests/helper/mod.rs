@@ -3,8 +3,12 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+// Each test crate only uses some of these.
+#![allow(dead_code)]
+
 #[cfg(test)]
 use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
 
 pub fn benches_dir() -> PathBuf {
     Path::new(file!())
@@ -16,3 +20,75 @@ pub fn benches_dir() -> PathBuf {
         .unwrap()
         .join("benches")
 }
+
+/// An empty directory for the files of one test,
+/// which is removed again when dropped,
+/// even if the test fails.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates the directory, named after `name` and the test process.
+    /// Anything left over from a previous run is removed first.
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("mle-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        Self(dir)
+    }
+
+    /// Writes `content` to the file at `rel_path`,
+    /// creating its parent directories as required.
+    pub fn write(&self, rel_path: &str, content: impl AsRef<[u8]>) {
+        let path = self.0.join(rel_path);
+        std::fs::create_dir_all(path.parent().expect("Has a parent"))
+            .expect("Failed to create dir");
+        std::fs::write(path, content).expect("Failed to write");
+    }
+
+    /// Reads the file at `rel_path`.
+    pub fn read(&self, rel_path: &str) -> String {
+        std::fs::read_to_string(self.0.join(rel_path)).expect("Failed to read")
+    }
+}
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Runs the `mle` binary in `cwd`.
+pub fn mle(cwd: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_mle"))
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .expect("Failed to run mle")
+}
+
+/// Runs the `mle` binary in `cwd`,
+/// and returns its standard output,
+/// failing if it did not succeed.
+pub fn run_mle(cwd: &Path, args: &[&str]) -> String {
+    let output = mle(cwd, args);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("UTF-8 output")
+}
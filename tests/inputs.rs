@@ -0,0 +1,457 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+#[cfg(test)]
+mod helper;
+
+mod archives {
+    use std::io::Write;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use crate::helper::TempDir;
+    use cli_utils::path_buf::PathBuf;
+    use flate2::{Compression, write::GzEncoder};
+    use mle::config::Extractor as Config;
+
+    const INDEX: &str = r#"<html><body>
+<a href="guide/intro.html#start">Intro</a>
+<a href="/index.html">Home</a>
+</body></html>"#;
+    const INTRO: &str = "# Intro\n\n[back](../index.html) [top](/README.md)\n";
+
+    fn write_zip(path: &Path) {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).expect("Failed to create"));
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, content) in [
+            ("index.html", INDEX),
+            ("guide/intro.md", INTRO),
+            ("logo.png", "\u{0}not markup"),
+        ] {
+            zip.start_file(name, options)
+                .expect("Failed to add zip entry");
+            zip.write_all(content.as_bytes())
+                .expect("Failed to write zip entry");
+        }
+        zip.finish().expect("Failed to finish zip");
+    }
+
+    fn write_tar_gz(path: &Path) {
+        let gz = GzEncoder::new(
+            std::fs::File::create(path).expect("Failed to create"),
+            Compression::default(),
+        );
+        let mut tar = tar::Builder::new(gz);
+        for (name, content) in [("./index.html", INDEX), ("./guide/intro.md", INTRO)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, name, content.as_bytes())
+                .expect("Failed to add tar entry");
+        }
+        tar.into_inner()
+            .expect("Failed to finish tar")
+            .finish()
+            .expect("Failed to finish gzip");
+    }
+
+    fn write_gz(path: &Path, content: &str) {
+        let mut gz = GzEncoder::new(
+            std::fs::File::create(path).expect("Failed to create"),
+            Compression::default(),
+        );
+        gz.write_all(content.as_bytes()).expect("Failed to write");
+        gz.finish().expect("Failed to finish gzip");
+    }
+
+    /// Extracts links, and returns them as (source, canonical target) pairs.
+    async fn extract(file: &Path) -> Vec<(String, String)> {
+        extract_with(Config {
+            markup_files: vec![PathBuf::from_str(&file.display().to_string()).expect("Infallible")],
+            ..Config::default()
+        })
+        .await
+    }
+
+    async fn extract_with(conf: Config) -> Vec<(String, String)> {
+        let (links, _anchors, errors) = mle::find_all_links(&conf).await;
+        assert!(errors.is_empty(), "{errors:?}");
+        let base = PathBuf::from_str("/").expect("Infallible");
+        links
+            .iter()
+            .map(|link| {
+                (
+                    link.source.to_string(),
+                    link.target
+                        .canonical(false, link.source.file.clone(), &base)
+                        .expect("Canonicalization to succeed")
+                        .to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn expected(archive: &Path) -> Vec<(String, String)> {
+        let arc = archive.display();
+        [
+            ("index.html:2:10", "guide/intro.html#start"),
+            ("index.html:3:10", "index.html"),
+            ("guide/intro.md:3:1", "index.html"),
+            ("guide/intro.md:3:23", "README.md"),
+        ]
+        .iter()
+        .map(|(source, target)| (format!("{arc}!/{source}"), format!("{arc}!/{target}")))
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn zip() {
+        let dir = TempDir::new("zip");
+        let archive = dir.join("docs.zip");
+        write_zip(&archive);
+        assert_eq!(expected(&archive), extract(&archive).await);
+    }
+
+    #[tokio::test]
+    async fn zip_with_markup_type() {
+        let dir = TempDir::new("zip-markup-type");
+        let archive = dir.join("docs.zip");
+        write_zip(&archive);
+        let found = extract_with(Config {
+            markup_files: vec![
+                PathBuf::from_str(&archive.display().to_string()).expect("Infallible"),
+            ],
+            markup_type: Some(mle::markup::Type::Markdown),
+            ..Config::default()
+        })
+        .await;
+        assert_eq!(expected(&archive), found);
+    }
+
+    #[tokio::test]
+    async fn tar_gz() {
+        let dir = TempDir::new("tar-gz");
+        let archive = dir.join("docs.tar.gz");
+        write_tar_gz(&archive);
+        assert_eq!(expected(&archive), extract(&archive).await);
+    }
+
+    #[tokio::test]
+    async fn gz() {
+        let dir = TempDir::new("gz");
+        let file = dir.join("CHANGES.md.gz");
+        write_gz(&file, INTRO);
+        let found = extract(&file).await;
+        let file_str = file.display().to_string();
+        assert_eq!(
+            vec![
+                (
+                    format!("{file_str}:3:1"),
+                    dir.join("../index.html").display().to_string()
+                ),
+                (format!("{file_str}:3:23"), "/README.md".to_owned()),
+            ],
+            found
+        );
+    }
+}
+
+mod content_sources {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::process::{Command, Stdio};
+    use std::str::FromStr;
+    use std::thread;
+
+    use cli_utils::path_buf::PathBuf;
+    use mle::config::Extractor as Config;
+
+    const PAGE: &str = "# Page
+
+[sibling](other.md) [up](../up.md#sec) [root](/root.md) [abs](https://example.net/)
+";
+
+    /// Serves `body` exactly once on a random local port,
+    /// and returns the URL of the served page.
+    fn serve_once(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind a local port");
+        let port = listener.local_addr().expect("Bound address").port();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("Failed to accept connection");
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).expect("Failed to read request");
+            write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/markdown\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .expect("Failed to write response");
+        });
+        format!("http://127.0.0.1:{port}/docs/page.md")
+    }
+
+    #[tokio::test]
+    async fn url() {
+        let page_url = serve_once(PAGE);
+        let conf = Config {
+            markup_files: vec![PathBuf::from_str(&page_url).expect("Infallible")],
+            ..Config::default()
+        };
+        let (links, _anchors, errors) = mle::find_all_links(&conf).await;
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(links[0].source.file.to_string(), page_url);
+        let base = PathBuf::from_str(".").expect("Infallible");
+        let canonical: Vec<String> = links
+            .iter()
+            .map(|link| {
+                link.target
+                    .canonical(false, link.source.file.clone(), &base)
+                    .expect("Canonicalization to succeed")
+                    .to_string()
+            })
+            .collect();
+        let host = page_url.trim_end_matches("/docs/page.md");
+        assert_eq!(
+            vec![
+                format!("{host}/docs/other.md"),
+                format!("{host}/up.md#sec"),
+                format!("{host}/root.md"),
+                "https://example.net/".to_owned(),
+            ],
+            canonical
+        );
+    }
+
+    #[test]
+    fn stdin() {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mle"))
+            .args(["--markup-type", "md", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to run mle");
+        child
+            .stdin
+            .take()
+            .expect("Piped stdin")
+            .write_all(PAGE.as_bytes())
+            .expect("Failed to write to stdin");
+        let output = child.wait_with_output().expect("Failed to wait for mle");
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "L:-:3:1:other.md",
+                "L:-:3:21:../up.md#sec",
+                "L:-:3:40:/root.md",
+                "L:-:3:57:https://example.net/",
+            ]
+        );
+    }
+}
+
+mod encodings {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use crate::helper::TempDir;
+    use cli_utils::path_buf::PathBuf;
+    use mle::config::Extractor as Config;
+    use mle::encoding::{DecodingError, Reason};
+
+    /// "Bücher" (books) in ISO-8859-1
+    const LATIN1_TARGET: &[u8] = b"b\xFCcher.html";
+
+    fn write_html(path: &Path, head: &[u8]) {
+        let mut content = b"<html><head>".to_vec();
+        content.extend(head);
+        content.extend(b"</head><body><a href=\"");
+        content.extend(LATIN1_TARGET);
+        content.extend(b"\">B\xFCcher</a></body></html>");
+        std::fs::write(path, content).expect("Failed to write");
+    }
+
+    async fn extract(file: &Path, default_encoding: Option<&str>) -> mle::BoxResult<Vec<String>> {
+        let conf = Config {
+            markup_files: vec![PathBuf::from_str(&file.display().to_string()).expect("Infallible")],
+            default_encoding: default_encoding.map(ToOwned::to_owned),
+            ..Config::default()
+        };
+        let (links, _anchors, mut errors) = mle::find_all_links(&conf).await;
+        if let Some(err) = errors.pop() {
+            return Err(err);
+        }
+        Ok(links.iter().map(|link| link.target.to_string()).collect())
+    }
+
+    #[tokio::test]
+    async fn declared() {
+        let dir = TempDir::new("encoding-declared");
+        let file = dir.join("index.html");
+        write_html(&file, br#"<meta charset="ISO-8859-1">"#);
+        assert_eq!(
+            vec!["bücher.html".to_owned()],
+            extract(&file, None).await.expect("Decoding to succeed")
+        );
+    }
+
+    #[tokio::test]
+    async fn default() {
+        let dir = TempDir::new("encoding-default");
+        let file = dir.join("index.html");
+        write_html(&file, b"");
+        assert_eq!(
+            vec!["bücher.html".to_owned()],
+            extract(&file, Some("latin1"))
+                .await
+                .expect("Decoding to succeed")
+        );
+    }
+
+    #[tokio::test]
+    async fn undecodable() {
+        let dir = TempDir::new("encoding-undecodable");
+        let file = dir.join("index.html");
+        write_html(&file, b"");
+        let err = extract(&file, None)
+            .await
+            .expect_err("Decoding to fail without a default encoding");
+        let io_err = err.downcast_ref::<std::io::Error>().expect("An IO error");
+        assert_eq!(std::io::ErrorKind::InvalidData, io_err.kind());
+        let decoding_err = io_err
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<DecodingError>())
+            .expect("A decoding error");
+        assert_eq!(Reason::Assumed, decoding_err.reason);
+    }
+}
+
+mod git_revision {
+    use std::path::Path;
+    use std::process::Command;
+    use std::str::FromStr;
+
+    use crate::helper::TempDir;
+    use cli_utils::path_buf::PathBuf;
+    use mle::config::Extractor as Config;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.net"])
+            .args(args)
+            .status()
+            .expect("Failed to run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// Creates a repository with a tagged and a later version of a file,
+    /// and leaves a third version uncommitted in the working tree.
+    fn create_repo() -> TempDir {
+        let repo = TempDir::new("git-rev");
+        git(&repo, &["init", "--quiet"]);
+        repo.write("docs/index.md", "[old](https://example.net/v1)\n");
+        git(&repo, &["add", "docs/index.md"]);
+        git(&repo, &["commit", "--quiet", "-m", "v1"]);
+        git(&repo, &["tag", "v1"]);
+        repo.write("docs/index.md", "[new](https://example.net/v2)\n");
+        git(&repo, &["commit", "--quiet", "-a", "-m", "v2"]);
+        repo.write("docs/index.md", "[wip](https://example.net/wip)\n");
+        repo
+    }
+
+    async fn extract(doc: &str, git_rev: Option<&str>) -> (Vec<String>, Vec<String>) {
+        let conf = Config {
+            markup_files: vec![PathBuf::from_str(doc).expect("Infallible")],
+            git_rev: git_rev.map(ToOwned::to_owned),
+            ..Config::default()
+        };
+        let (links, _anchors, errors) = mle::find_all_links(&conf).await;
+        assert!(errors.is_empty(), "{errors:?}");
+        (
+            links.iter().map(|link| link.source.to_string()).collect(),
+            links.iter().map(|link| link.target.to_string()).collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn revisions() {
+        let repo = create_repo();
+        let doc = repo.join("docs/index.md").display().to_string();
+
+        let (sources, targets) = extract(&doc, Some("v1")).await;
+        assert_eq!(vec![format!("v1:{doc}:1:1")], sources);
+        assert_eq!(vec!["https://example.net/v1"], targets);
+
+        let (sources, targets) = extract(&doc, Some("HEAD")).await;
+        assert_eq!(vec![format!("HEAD:{doc}:1:1")], sources);
+        assert_eq!(vec!["https://example.net/v2"], targets);
+
+        let (sources, targets) = extract(&doc, None).await;
+        assert_eq!(vec![format!("{doc}:1:1")], sources);
+        assert_eq!(vec!["https://example.net/wip"], targets);
+
+        let conf = Config {
+            markup_files: vec![PathBuf::from_str(&doc).expect("Infallible")],
+            git_rev: Some("no-such-rev".to_owned()),
+            ..Config::default()
+        };
+        let (_links, _anchors, errors) = mle::find_all_links(&conf).await;
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn archives_at_revision() {
+        let repo = create_repo();
+        repo.write("docs/old.md.gz", "not really gzip");
+        repo.write("docs.zip", "not really zip");
+        git(&repo, &["add", "docs/old.md.gz", "docs.zip"]);
+        git(&repo, &["commit", "--quiet", "-m", "archives"]);
+        for archive in ["docs/old.md.gz", "docs.zip"] {
+            let conf = Config {
+                markup_files: vec![PathBuf::from(repo.join(archive))],
+                git_rev: Some("HEAD".to_owned()),
+                ..Config::default()
+            };
+            let (links, _anchors, errors) = mle::find_all_links(&conf).await;
+            assert!(links.is_empty());
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            assert!(errors[0].to_string().contains("git revision"), "{errors:?}");
+        }
+    }
+}
+
+mod type_detection {
+    use std::str::FromStr;
+
+    use cli_utils::path_buf::PathBuf;
+    use mle::config::Extractor as Config;
+
+    const DIR: &str = "./benches/type_detection";
+
+    #[tokio::test]
+    async fn find_all_links() {
+        let conf = Config {
+            markup_files: ["README", "page", "intro.mdx.txt"]
+                .iter()
+                .map(|file_name| {
+                    PathBuf::from_str(&format!("{DIR}/{file_name}")).expect("Infallible")
+                })
+                .collect(),
+            type_overrides: vec!["*.mdx.txt=mdx".parse().expect("Valid override")],
+            sniff_content: true,
+            ..Config::default()
+        };
+        let (links, _anchors, errors) = mle::find_all_links(&conf).await;
+        assert!(errors.is_empty());
+        let targets: Vec<String> = links.iter().map(|link| link.target.to_string()).collect();
+        assert_eq!(
+            vec!["docs/index.md", "https://example.net/", "/docs/intro"],
+            targets
+        );
+    }
+}
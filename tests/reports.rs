@@ -0,0 +1,608 @@
+// SPDX-FileCopyrightText: 2026 Robin Vobruba <hoijui.quaero@gmail.com>
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+#[cfg(test)]
+mod helper;
+
+mod backlinks {
+    use crate::helper::{TempDir, run_mle};
+
+    #[test]
+    fn index() {
+        let dir = TempDir::new("backlinks");
+        dir.write(
+            "README.md",
+            "[setup](docs/setup.md)\n\n[install](docs/setup.md#install)\n",
+        );
+        dir.write(
+            "docs/faq.md",
+            "# FAQ\n\n[install](setup.md#install) [top](#faq)\n",
+        );
+        assert_eq!(
+            run_mle(
+                &dir,
+                &["README.md", "docs/faq.md", "--no-links", "--backlinks"]
+            ),
+            "docs/faq.md#faq
+  docs/faq.md:3:29
+docs/setup.md
+  README.md:1:1
+docs/setup.md#install
+  README.md:3:1
+  docs/faq.md:3:1
+"
+        );
+    }
+
+    #[test]
+    fn canonical_and_site_root() {
+        let dir = TempDir::new("backlinks-canonical");
+        dir.write("README.md", "[a](/docs/a.md)\n");
+        dir.write("docs/a.md", "[home](../README.md)\n");
+        let args = ["README.md", "docs/a.md", "--no-links", "--backlinks"];
+        let expected = "README.md\n  docs/a.md:1:1\ndocs/a.md\n  README.md:1:1\n";
+        assert_eq!(
+            run_mle(&dir, &[&args[..], &["--site-root", "."]].concat()),
+            expected
+        );
+        assert_eq!(
+            run_mle(
+                &dir,
+                &[&args[..], &["--canonical", "--site-root", "."]].concat()
+            ),
+            expected
+        );
+        assert_eq!(
+            run_mle(&dir, &[&args[..], &["--canonical"]].concat()),
+            "/docs/a.md\n  README.md:1:1\nREADME.md\n  docs/a.md:1:1\n"
+        );
+    }
+}
+
+mod baseline {
+    use std::process::Command;
+
+    use crate::helper::{TempDir, mle, run_mle};
+
+    #[test]
+    fn only_new_findings() {
+        let dir = TempDir::new("baseline");
+        dir.write("page.md", "[old](gone.md)\n\n[ok](page.md)\n");
+        run_mle(
+            &dir,
+            &[
+                "page.md",
+                "--validate",
+                "broken.txt",
+                "--write-baseline",
+                "baseline.json",
+            ],
+        );
+
+        // Shifts the known links down, and adds a new broken one
+        dir.write(
+            "page.md",
+            "# Title\n\n[old](gone.md)\n\n[ok](page.md)\n\n[new](missing.md)\n",
+        );
+        let output = mle(
+            &dir,
+            &[
+                "page.md",
+                "--validate",
+                "broken.txt",
+                "--baseline",
+                "baseline.json",
+            ],
+        );
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("1 broken local links found"));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "L:page.md:7:1:missing.md\n"
+        );
+        assert_eq!(
+            dir.read("broken.txt"),
+            "B:missing-file:page.md:7:1:missing.md\n"
+        );
+    }
+
+    #[test]
+    fn from_stdin() {
+        use std::io::Write as _;
+
+        let dir = TempDir::new("baseline-stdin");
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mle"))
+            .current_dir(&dir)
+            .args(["-", "--markup-type", "md"])
+            .args(["--write-baseline", "baseline.json"])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .expect("Failed to run mle");
+        child
+            .stdin
+            .take()
+            .expect("Piped stdin")
+            .write_all(b"See  [a](a.md)\n")
+            .expect("Failed to write to stdin");
+        assert!(child.wait().expect("Failed to wait for mle").success());
+        let baseline = dir.read("baseline.json");
+        assert!(
+            baseline.contains(r#""context": "See [a](a.md)""#),
+            "{baseline}"
+        );
+    }
+}
+
+mod diff {
+    use crate::helper::{TempDir, run_mle};
+
+    #[test]
+    fn roots_and_results() {
+        let dir = TempDir::new("diff");
+        dir.write(
+            "old/README.md",
+            "# Intro\n\n[a](a.md) [gone](gone.md)\n\n[ext](https://example.org)\n",
+        );
+        dir.write("old/a.md", "# A\n");
+        dir.write(
+            "new/README.md",
+            "# Intro\n\nNew paragraph.\n\n[a](a.md) [new](new.md)\n",
+        );
+        dir.write("new/a.md", "# A\n\n[ext](https://example.org)\n");
+
+        let text = run_mle(
+            &dir,
+            &["README.md", "a.md", "--diff", "old", "new", "--anchors"],
+        );
+        assert_eq!(
+            text,
+            "-L:README.md:3:11:gone.md
++L:README.md:5:11:new.md
+~L:a.md:3:1:https://example.org/ (from README.md:5:1)
+"
+        );
+
+        let old_json = run_mle(&dir.join("old"), &["README.md", "a.md", "-F", "json"]);
+        let new_json = run_mle(&dir.join("new"), &["README.md", "a.md", "-F", "json"]);
+        dir.write("old.json", old_json);
+        dir.write("new.json", new_json);
+        let csv = run_mle(&dir, &["--diff", "old.json", "new.json", "-F", "csv"]);
+        assert_eq!(
+            csv,
+            "change;value;old_file;old_line;old_column;new_file;new_line;new_column
+removed;gone.md;README.md;3;11;;;
+added;new.md;;;;README.md;5;11
+moved;https://example.org/;README.md;5;1;a.md;3;1
+"
+        );
+        let dot = run_mle(
+            &dir,
+            &["--diff", "old.json", "new.json", "--no-links", "--graph"],
+        );
+        assert!(dot.contains(r#""README.md" -> "gone.md" [label="1", count=1, fragments="", change="removed", color=red];"#), "{dot}");
+    }
+}
+
+mod graph {
+    use crate::helper::{TempDir, mle, run_mle};
+
+    #[test]
+    fn dot() {
+        let dir = TempDir::new("graph");
+        dir.write(
+            "README.md",
+            "[setup](docs/setup.md#install) [again](docs/setup.md) [web](https://example.org/)\n",
+        );
+        dir.write("docs/setup.md", "[home](../README.md)\n");
+        assert_eq!(
+            run_mle(
+                &dir,
+                &[
+                    "README.md",
+                    "docs/setup.md",
+                    "--no-links",
+                    "--graph",
+                    "--graph-format",
+                    "dot",
+                ]
+            ),
+            r#"digraph links {
+  "README.md" [kind="document", shape=box];
+  "docs/setup.md" [kind="document", shape=box];
+  "example.org" [kind="host", shape=ellipse];
+  "README.md" -> "docs/setup.md" [label="2", count=2, fragments="install"];
+  "README.md" -> "example.org" [label="1", count=1, fragments=""];
+  "docs/setup.md" -> "README.md" [label="1", count=1, fragments=""];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn next_to_other_reports() {
+        let dir = TempDir::new("graph-reports");
+        dir.write("README.md", "[gone](gone.md)\n");
+        let output = mle(
+            &dir,
+            &[
+                "README.md",
+                "--validate",
+                "broken.txt",
+                "--graph",
+                "graph.json",
+                "--graph-format",
+                "json",
+            ],
+        );
+        assert!(String::from_utf8_lossy(&output.stderr).contains("1 broken local links found"));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "L:README.md:1:1:gone.md\n"
+        );
+        assert_eq!(
+            dir.read("broken.txt"),
+            "B:missing-file:README.md:1:1:gone.md\n"
+        );
+        let graph: serde_json::Value =
+            serde_json::from_str(&dir.read("graph.json")).expect("Valid JSON");
+        assert_eq!(
+            graph["edges"][0],
+            serde_json::json!({
+                "source": "README.md",
+                "target": "gone.md",
+                "count": 1,
+                "fragments": [],
+            })
+        );
+    }
+}
+
+mod grouped {
+    use crate::helper::{TempDir, run_mle};
+
+    #[test]
+    fn group_by_target() {
+        let dir = TempDir::new("grouped");
+        dir.write(
+            "a.md",
+            "[x](https://example.org/guide#install)\n\n[y](https://example.org/guide)\n",
+        );
+        dir.write("b.md", "[z](https://example.org/guide#usage)\n");
+        let output = run_mle(&dir, &["a.md", "b.md", "--group-by-target", "-F", "json"]);
+        let groups: serde_json::Value =
+            serde_json::from_str(&output).expect("Output is valid JSON");
+        assert_eq!(groups.as_array().map(Vec::len), Some(1));
+        assert_eq!(groups[0]["target"], "https://example.org/guide");
+        assert_eq!(groups[0]["count"], 3);
+        assert_eq!(
+            groups[0]["fragments"],
+            serde_json::json!(["install", "usage"])
+        );
+        assert_eq!(groups[0]["sources"][2]["file"], "b.md");
+    }
+}
+
+mod ignored {
+    use crate::helper::{TempDir, mle, run_mle};
+
+    const PAGE: &str = "# Intro\n\n## Contents\n\n[a](https://example.com/a) [b](other.md)\n";
+
+    #[test]
+    fn report() {
+        let dir = TempDir::new("ignored");
+        dir.write("page.md", PAGE);
+        dir.write("vendor/lib.md", PAGE);
+        let output = mle(
+            &dir,
+            &[
+                "page.md",
+                "vendor/lib.md",
+                "--anchors",
+                "anchors.txt",
+                "--ignored",
+                "ignored.txt",
+                "--ignore-links",
+                "https://*",
+                "*.pdf",
+                "--ignore-anchors",
+                "cont*",
+                "--ignore-files",
+                "vendor/*",
+            ],
+        );
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "L:page.md:5:28:other.md\n"
+        );
+        assert_eq!(dir.read("anchors.txt"), "A:page.md:1:1#intro\n");
+        assert_eq!(
+            dir.read("ignored.txt"),
+            "link 'https://*': 1 ignored
+  page.md:5:1:https://example.com/a
+link '*.pdf': 0 ignored
+anchor 'cont*': 1 ignored
+  page.md:3:1#contents
+file 'vendor/*': 1 ignored
+  vendor/lib.md
+"
+        );
+        assert!(String::from_utf8_lossy(&output.stderr).contains("'*.pdf'"));
+    }
+
+    #[test]
+    fn report_only() {
+        let dir = TempDir::new("ignored-only");
+        dir.write("page.md", PAGE);
+        let output = run_mle(
+            &dir,
+            &[
+                "page.md",
+                "--no-links",
+                "--ignored",
+                "--ignore-links",
+                "https://*",
+                "--ignore-anchors",
+                "cont*",
+            ],
+        );
+        assert_eq!(
+            output,
+            "link 'https://*': 1 ignored
+  page.md:5:1:https://example.com/a
+anchor 'cont*': 1 ignored
+  page.md:3:1#contents
+"
+        );
+    }
+}
+
+mod orphans {
+    use crate::helper::{TempDir, run_mle};
+
+    #[test]
+    fn report() {
+        let dir = TempDir::new("orphans");
+        dir.write("README.md", "[a](docs/a.md)\n");
+        dir.write("docs/a.md", "[home](../README.md)\n");
+        dir.write("docs/b.md", "# No links here\n");
+        dir.write("docs/c.md", "[d](d.md)\n");
+        dir.write("docs/d.md", "[c](c.md)\n");
+        let output = run_mle(
+            &dir,
+            &[
+                "./README.md",
+                "docs/a.md",
+                "docs/b.md",
+                "docs/c.md",
+                "docs/d.md",
+                "--no-links",
+                "--orphans",
+                "orphans.txt",
+            ],
+        );
+        assert_eq!(output, "");
+        assert_eq!(
+            dir.read("orphans.txt"),
+            "orphan:docs/b.md
+unreachable:docs/b.md
+unreachable:docs/c.md
+unreachable:docs/d.md
+"
+        );
+    }
+
+    #[test]
+    fn root_relative_links_with_site_root() {
+        let dir = TempDir::new("orphans-site-root");
+        dir.write("README.md", "[a](/docs/a.md)\n");
+        dir.write("docs/a.md", "[home](../README.md)\n");
+        let args = ["README.md", "docs/a.md", "--no-links", "--orphans"];
+        assert_eq!(
+            run_mle(&dir, &[&args[..], &["--site-root", "."]].concat()),
+            ""
+        );
+        // Without a site root, the link points to the root of the file-system
+        assert_eq!(
+            run_mle(&dir, &args),
+            "orphan:docs/a.md\nunreachable:docs/a.md\n"
+        );
+    }
+}
+
+mod remote_cache {
+    use crate::helper::{TempDir, run_mle};
+
+    const PAGE: &str = "[a](https://example.org/guide.html#install) \
+[b](https://example.org/guide.html#usage) \
+[c](https://example.org/gone.html#x) \
+[d](https://example.org/new.html#x)
+";
+
+    const CACHE: &str = r#"{
+  "https://example.org/gone.html": {"Unavailable": "404 Not Found"},
+  "https://example.org/guide.html": {"Available": [{
+    "source": {"file": {"Url": "https://example.org/guide.html"}, "pos": {"line": 3, "column": 1}},
+    "name": "install",
+    "type": "TitleAuto"
+  }]}
+}"#;
+
+    #[test]
+    fn annotate_remote_fragments() {
+        let dir = TempDir::new("remote-cache");
+        dir.write("page.md", PAGE);
+        dir.write("cache.json", CACHE);
+        let output = run_mle(
+            &dir,
+            &[
+                "page.md",
+                "--result-format",
+                "json",
+                "--result-extended",
+                "--remote-cache",
+                "cache.json",
+            ],
+        );
+        let links: serde_json::Value = serde_json::from_str(&output).expect("Valid JSON output");
+        let annotations: Vec<_> = links
+            .as_array()
+            .expect("A list of links")
+            .iter()
+            .map(|link| link["trg_remote_fragment"].clone())
+            .collect();
+        assert_eq!(
+            annotations,
+            vec![
+                serde_json::json!("found"),
+                serde_json::json!("missing"),
+                serde_json::json!("unavailable"),
+                serde_json::Value::Null,
+            ]
+        );
+        let saved: serde_json::Value =
+            serde_json::from_str(&dir.read("cache.json")).expect("Valid JSON cache");
+        assert_eq!(
+            saved["https://example.org/gone.html"]["Unavailable"],
+            "404 Not Found"
+        );
+    }
+}
+
+mod summary {
+    use crate::helper::{TempDir, run_mle};
+
+    #[test]
+    fn json() {
+        let dir = TempDir::new("summary");
+        dir.write(
+            "README.md",
+            "# Intro\n\n[a](https://example.org/a) [b](https://example.org/b) [c](other.md)\n",
+        );
+        dir.write("other.md", "# Other\n");
+        let output = run_mle(
+            &dir,
+            &[
+                "README.md",
+                "other.md",
+                "--result-format",
+                "json",
+                "--links-file",
+                "links.json",
+                "--summary",
+            ],
+        );
+        let summary: serde_json::Value = serde_json::from_str(&output).expect("Valid JSON summary");
+        assert_eq!(summary["files"], 2);
+        assert_eq!(summary["links"], 3);
+        assert_eq!(summary["anchors"], 2);
+        assert_eq!(summary["links_per_file"]["other.md"], 0);
+        assert_eq!(summary["links_per_kind"]["http"], 2);
+        assert_eq!(
+            summary["top_external_domains"],
+            serde_json::json!([["example.org", 2]])
+        );
+        assert!(dir.join("links.json").exists());
+    }
+}
+
+mod validate {
+    use crate::helper::{TempDir, mle, run_mle};
+
+    const PAGE: &str = "# Intro
+
+[ok](other.md#sec) [bad anchor](other.md#nope) [missing](gone.md)
+
+[self](#intro) [bad self](#nah) [remote](https://example.com/#x) [unscanned](data.txt#x)
+
+[no anchors](empty.md#x)
+";
+
+    const OTHER: &str = "# Sec\n\n[back](page.md)\n";
+
+    #[test]
+    fn broken_links() {
+        let dir = TempDir::new("validate");
+        dir.write("page.md", PAGE);
+        dir.write("other.md", OTHER);
+        dir.write("data.txt", "data");
+        dir.write("empty.md", "Neither links nor anchors\n");
+        let output = mle(
+            &dir,
+            &[
+                "page.md",
+                "other.md",
+                "empty.md",
+                "--no-links",
+                "--validate",
+                "broken.txt",
+            ],
+        );
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("4 broken local links found"));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+        assert_eq!(
+            dir.read("broken.txt"),
+            "B:missing-anchor:page.md:3:20:other.md#nope
+B:missing-file:page.md:3:48:gone.md
+B:missing-anchor:page.md:5:16:#nah
+B:missing-anchor:page.md:7:1:empty.md#x
+"
+        );
+    }
+
+    #[test]
+    fn all_fine() {
+        let dir = TempDir::new("validate-ok");
+        dir.write("other.md", OTHER);
+        dir.write("page.md", "[sec](other.md#sec)\n");
+        let output = run_mle(
+            &dir,
+            &[
+                "page.md",
+                "other.md",
+                "--result-format",
+                "json",
+                "--validate",
+            ],
+        );
+        assert!(output.starts_with("[]"));
+    }
+
+    #[test]
+    fn markdown_report_only() {
+        let dir = TempDir::new("validate-md");
+        dir.write("page.md", "[gone](gone.md)\n");
+        let output = mle(&dir, &["page.md", "--no-links", "--validate", "-F", "md"]);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!stderr.contains("panicked"), "{stderr}");
+        assert!(stderr.contains("1 broken local links found"), "{stderr}");
+        assert!(String::from_utf8_lossy(&output.stdout).contains("| `page.md:1:1` | `gone.md` |"));
+    }
+
+    #[test]
+    fn percent_encoded_fragment() {
+        let dir = TempDir::new("validate-pct");
+        dir.write(
+            "page.md",
+            "# Café Über {#café-über}\n\n[b](#caf%C3%A9-%C3%BCber)\n",
+        );
+        run_mle(&dir, &["page.md", "--no-links", "--validate"]);
+    }
+
+    #[test]
+    fn github_title_anchors() {
+        let dir = TempDir::new("validate-gfm");
+        dir.write(
+            "page.md",
+            "# Foo_Bar Über\n\n# Intro\n\n# Intro\n\n[a](#foo_bar-über) [b](#intro-1)\n",
+        );
+        run_mle(&dir, &["page.md", "--no-links", "--validate"]);
+    }
+}